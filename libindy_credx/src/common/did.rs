@@ -0,0 +1,57 @@
+use crate::utils::validation::{Validatable, ValidationError};
+
+pub const DID_PREFIX: &str = "did";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct DidValue(pub String);
+
+impl DidValue {
+    pub fn new(did: &str, method: Option<&str>) -> Self {
+        match method {
+            Some(method) => Self([DID_PREFIX, method, did].join(":")),
+            None => Self(did.to_owned()),
+        }
+    }
+
+    fn parts(&self) -> Option<(&str, &str)> {
+        let parts: Vec<&str> = self.0.splitn(3, ':').collect();
+        if parts.len() == 3 && parts[0] == DID_PREFIX {
+            Some((parts[1], parts[2]))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_method(&self) -> Option<&str> {
+        self.parts().map(|(method, _)| method)
+    }
+
+    pub fn default_method(&self, method: Option<&str>) -> Self {
+        match (self.get_method(), method) {
+            (None, Some(method)) => Self::new(&self.0, Some(method)),
+            _ => self.clone(),
+        }
+    }
+
+    pub fn remove_method(&self, method: &str) -> Self {
+        match self.parts() {
+            Some((found, value)) if found == method => Self(value.to_owned()),
+            _ => self.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for DidValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Validatable for DidValue {
+    fn validate(&self) -> Result<(), ValidationError> {
+        if self.0.is_empty() {
+            return Err(invalid!("DidValue validation failed: empty DID"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod did;
+pub mod error;
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use ursa::cl::{
+    CredentialSignature, RevocationRegistry as CryptoRevocationRegistry,
+    SignatureCorrectnessProof, Witness,
+};
+use ursa::hash::sha2::{Digest, Sha256};
+
+use crate::identifiers::cred_def::CredentialDefinitionId;
+use crate::identifiers::rev_reg_def::RevocationRegistryDefinitionId;
+use crate::identifiers::schema::SchemaId;
+use crate::utils::validation::{Validatable, ValidationError};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributeValues {
+    pub raw: String,
+    pub encoded: String,
+}
+
+impl AttributeValues {
+    /// Encode a raw attribute value the way the Indy anoncreds convention
+    /// requires: a value that is (or stringifies to) a signed 32-bit integer
+    /// encodes as that integer's decimal string; everything else encodes as
+    /// the big-endian unsigned decimal of its SHA-256 hash. This is what lets
+    /// verifiers re-derive the same encoded value a ledger already has, so
+    /// credentials built through this path stay verifiable.
+    pub fn encode(raw: &str) -> String {
+        if let Ok(value) = raw.parse::<i32>() {
+            return value.to_string();
+        }
+        let mut hasher = Sha256::default();
+        hasher.input(raw.as_bytes());
+        decimal_from_be_bytes(&hasher.result())
+    }
+
+    /// Build an `AttributeValues` from a raw (already-stringified) attribute
+    /// value, computing `encoded` via [`Self::encode`].
+    pub fn from_raw(raw: impl Into<String>) -> Self {
+        let raw = raw.into();
+        let encoded = Self::encode(&raw);
+        Self { raw, encoded }
+    }
+}
+
+/// Render a big-endian byte slice as an unsigned decimal string, without
+/// pulling in a bigint dependency just for this one conversion.
+fn decimal_from_be_bytes(bytes: &[u8]) -> String {
+    let mut digits: Vec<u8> = vec![0];
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            let value = (*digit as u32) * 256 + carry;
+            *digit = (value % 10) as u8;
+            carry = value / 10;
+        }
+        while carry > 0 {
+            digits.push((carry % 10) as u8);
+            carry /= 10;
+        }
+    }
+    while digits.len() > 1 && *digits.last().unwrap() == 0 {
+        digits.pop();
+    }
+    digits.iter().rev().map(|d| (b'0' + d) as char).collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CredentialValues(pub HashMap<String, AttributeValues>);
+
+impl CredentialValues {
+    /// Build a `CredentialValues` from raw (already-stringified) attribute
+    /// values, encoding each one via [`AttributeValues::encode`].
+    pub fn from_raw_values(values: HashMap<String, String>) -> Self {
+        Self(
+            values
+                .into_iter()
+                .map(|(name, raw)| (name, AttributeValues::from_raw(raw)))
+                .collect(),
+        )
+    }
+}
+
+impl Validatable for CredentialValues {
+    fn validate(&self) -> Result<(), ValidationError> {
+        if self.0.is_empty() {
+            return Err(invalid!(
+                "CredentialValues validation failed: empty list of values has been passed"
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Credential {
+    pub schema_id: SchemaId,
+    pub cred_def_id: CredentialDefinitionId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg_id: Option<RevocationRegistryDefinitionId>,
+    pub values: CredentialValues,
+    pub signature: CredentialSignature,
+    pub signature_correctness_proof: SignatureCorrectnessProof,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg: Option<CryptoRevocationRegistry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness: Option<Witness>,
+}
+
+impl Validatable for Credential {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.schema_id.validate()?;
+        self.cred_def_id.validate()?;
+        self.values.validate()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_in_range_integer() {
+        assert_eq!(AttributeValues::encode("123"), "123");
+        assert_eq!(AttributeValues::encode("-123"), "-123");
+    }
+
+    #[test]
+    fn test_encode_out_of_range_integer_hashes() {
+        let encoded = AttributeValues::encode("99999999999999999999999999999999999999");
+        assert_ne!(encoded, "99999999999999999999999999999999999999");
+        assert!(encoded.chars().all(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_encode_non_numeric_string_hashes() {
+        let encoded = AttributeValues::encode("Alex");
+        assert!(encoded.chars().all(|c| c.is_ascii_digit()));
+        assert_ne!(encoded, "Alex");
+    }
+
+    #[test]
+    fn test_encode_is_deterministic() {
+        assert_eq!(AttributeValues::encode("Alex"), AttributeValues::encode("Alex"));
+    }
+
+    #[test]
+    fn test_encode_empty_string() {
+        let encoded = AttributeValues::encode("");
+        assert!(!encoded.is_empty());
+        assert!(encoded.chars().all(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_from_raw_values() {
+        let mut values = HashMap::new();
+        values.insert("age".to_string(), "25".to_string());
+        let cred_values = CredentialValues::from_raw_values(values);
+        let age = cred_values.0.get("age").unwrap();
+        assert_eq!(age.raw, "25");
+        assert_eq!(age.encoded, "25");
+    }
+}
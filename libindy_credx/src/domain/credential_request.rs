@@ -0,0 +1,33 @@
+use ursa::cl::{BlindedCredentialSecrets, BlindedCredentialSecretsCorrectnessProof, Nonce};
+
+use crate::common::did::DidValue;
+use crate::identifiers::cred_def::CredentialDefinitionId;
+use crate::utils::validation::{Validatable, ValidationError};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialRequest {
+    pub prover_did: DidValue,
+    pub cred_def_id: CredentialDefinitionId,
+    pub blinded_ms: BlindedCredentialSecrets,
+    pub blinded_ms_correctness_proof: BlindedCredentialSecretsCorrectnessProof,
+    pub nonce: Nonce,
+}
+
+impl Validatable for CredentialRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.prover_did.validate()?;
+        self.cred_def_id.validate()?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialRequestMetadata {
+    pub master_secret_blinding_data: ursa::cl::CredentialSecretsBlindingFactors,
+    pub nonce: Nonce,
+    pub master_secret_name: String,
+}
+
+impl Validatable for CredentialRequestMetadata {}
@@ -0,0 +1,18 @@
+pub const DELIMITER: char = ':';
+
+pub mod credential;
+pub mod credential_definition;
+pub mod credential_offer;
+pub mod credential_request;
+pub mod master_secret;
+pub mod proof;
+pub mod proof_request;
+pub mod requested_credential;
+pub mod revocation_registry;
+pub mod revocation_registry_definition;
+pub mod revocation_registry_delta;
+pub mod revocation_state;
+pub mod revocation_status_list;
+pub mod schema;
+pub mod w3c_credential;
+pub mod w3c_presentation;
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use ursa::cl::Proof as CryptoProof;
+
+use crate::identifiers::cred_def::CredentialDefinitionId;
+use crate::identifiers::rev_reg_def::RevocationRegistryDefinitionId;
+use crate::identifiers::schema::SchemaId;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Identifier {
+    pub schema_id: SchemaId,
+    pub cred_def_id: CredentialDefinitionId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg_id: Option<RevocationRegistryDefinitionId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevealedAttributeInfo {
+    pub sub_proof_index: u32,
+    pub raw: String,
+    pub encoded: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributeValue {
+    pub raw: String,
+    pub encoded: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevealedAttributeGroupInfo {
+    pub sub_proof_index: u32,
+    pub values: HashMap<String, AttributeValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubProofReferent {
+    pub sub_proof_index: u32,
+}
+
+/// Maps the referents from a `ProofRequestPayload` to what the prover
+/// actually supplied: revealed values (individually or in attribute
+/// groups), unrevealed/self-attested attributes, and which sub-proof each
+/// predicate was proved against.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RequestedProof {
+    #[serde(default)]
+    pub revealed_attrs: HashMap<String, RevealedAttributeInfo>,
+    #[serde(default)]
+    pub revealed_attr_groups: HashMap<String, RevealedAttributeGroupInfo>,
+    #[serde(default)]
+    pub self_attested_attrs: HashMap<String, String>,
+    #[serde(default)]
+    pub unrevealed_attrs: HashMap<String, SubProofReferent>,
+    #[serde(default)]
+    pub predicates: HashMap<String, SubProofReferent>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Proof {
+    pub proof: CryptoProof,
+    pub requested_proof: RequestedProof,
+    pub identifiers: Vec<Identifier>,
+}
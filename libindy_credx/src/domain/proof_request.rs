@@ -0,0 +1,272 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+use named_type::NamedType;
+use ursa::cl::Nonce;
+
+use crate::utils::validation::{Validatable, ValidationError};
+use crate::utils::wql::Query;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct NonRevocedInterval {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributeInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub names: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Query>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevocedInterval>,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PredicateTypes {
+    GE,
+    GT,
+    LE,
+    LT,
+}
+
+impl fmt::Display for PredicateTypes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PredicateTypes::GE => write!(f, "GE"),
+            PredicateTypes::GT => write!(f, "GT"),
+            PredicateTypes::LE => write!(f, "LE"),
+            PredicateTypes::LT => write!(f, "LT"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PredicateInfo {
+    pub name: String,
+    pub p_type: PredicateTypes,
+    pub p_value: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Query>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevocedInterval>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofRequestPayload {
+    pub nonce: Nonce,
+    pub name: String,
+    pub version: String,
+    pub requested_attributes: HashMap<String, AttributeInfo>,
+    pub requested_predicates: HashMap<String, PredicateInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevocedInterval>,
+}
+
+#[derive(Debug, Serialize, Deserialize, NamedType)]
+#[serde(tag = "ver")]
+pub enum ProofRequest {
+    #[serde(rename = "1.0")]
+    ProofRequestV1(ProofRequestPayload),
+    #[serde(rename = "2.0")]
+    ProofRequestV2(ProofRequestPayload),
+}
+
+impl ProofRequest {
+    pub fn value(&self) -> &ProofRequestPayload {
+        match self {
+            ProofRequest::ProofRequestV1(payload) => payload,
+            ProofRequest::ProofRequestV2(payload) => payload,
+        }
+    }
+}
+
+impl Validatable for ProofRequestPayload {
+    fn validate(&self) -> Result<(), ValidationError> {
+        if self.requested_attributes.is_empty() && self.requested_predicates.is_empty() {
+            return Err(invalid!(
+                "Proof Request validation failed: `requested_attributes` and `requested_predicates` are both empty"
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Validatable for ProofRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.value().validate()
+    }
+}
+
+/// Evaluates a WQL `Query` restriction against a flat credential tag set,
+/// using the anoncreds tag namespace: `schema_id`, `schema_name`,
+/// `schema_version`, `schema_issuer_did`, `issuer_did`, `cred_def_id`,
+/// `rev_reg_id`, plus per-attribute `attr::<name>::marker` (`"1"` when the
+/// credential carries that attribute) and `attr::<name>::value` (its raw
+/// value). A tag absent from `tags` never satisfies `$eq`/`$in`/`$ieq`/
+/// `$like`/the numeric comparisons, but does satisfy `$neq`. An empty
+/// restriction object parses to `Query::And(vec![])`, which matches
+/// everything since there are no clauses left to fail.
+///
+/// This is the prover-side counterpart to `Verifier::_do_process_operator`,
+/// which `verify_proof` uses to check the same restrictions against an
+/// already-received proof. The two are kept as separate evaluators rather
+/// than one shared implementation because the verifier's walk has to
+/// explain *which* tag/operator rejected a proof (diagnostics that a plain
+/// `bool` can't carry) and compares numeric operands of any size, where this
+/// lookup only compares values that fit in an `i64`; see the doc comment on `services::verifier::Filter` for the
+/// full rationale. `services::verifier::tests::
+/// do_process_operator_agrees_with_matches_restriction` cross-checks both
+/// evaluators against the same restrictions so they can't silently diverge
+/// on the operators they do share.
+pub fn matches_restriction(query: &Query, tags: &HashMap<String, String>) -> bool {
+    match query {
+        Query::Eq(tag, value) => tags.get(tag).map_or(false, |v| v == value),
+        Query::Neq(tag, value) => tags.get(tag).map_or(true, |v| v != value),
+        Query::In(tag, values) => tags.get(tag).map_or(false, |v| values.contains(v)),
+        Query::Ieq(tag, value) => tags
+            .get(tag)
+            .map_or(false, |v| v.eq_ignore_ascii_case(value)),
+        Query::Like(tag, pattern) => tags.get(tag).map_or(false, |v| like_matches(v, pattern)),
+        Query::Gt(tag, value) => {
+            numeric_cmp(tags.get(tag), value).map_or(false, |o| o == Ordering::Greater)
+        }
+        Query::Gte(tag, value) => {
+            numeric_cmp(tags.get(tag), value).map_or(false, |o| o != Ordering::Less)
+        }
+        Query::Lt(tag, value) => {
+            numeric_cmp(tags.get(tag), value).map_or(false, |o| o == Ordering::Less)
+        }
+        Query::Lte(tag, value) => {
+            numeric_cmp(tags.get(tag), value).map_or(false, |o| o != Ordering::Greater)
+        }
+        Query::And(clauses) => clauses.iter().all(|clause| matches_restriction(clause, tags)),
+        Query::Or(clauses) => clauses.iter().any(|clause| matches_restriction(clause, tags)),
+        Query::Not(clause) => !matches_restriction(clause, tags),
+    }
+}
+
+fn numeric_cmp(found: Option<&String>, value: &str) -> Option<Ordering> {
+    match (found?.parse::<i64>(), value.parse::<i64>()) {
+        (Ok(f), Ok(v)) => Some(f.cmp(&v)),
+        _ => None,
+    }
+}
+
+/// A minimal SQL-style `$like` matcher: `%` matches any run of characters,
+/// `_` matches exactly one, and `\%`/`\_` escape the wildcards to match them
+/// literally. No regex dependency is pulled in for this one restriction kind.
+fn like_matches(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    like_match(&value, &pattern)
+}
+
+fn like_match(value: &[char], pattern: &[char]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some(('\\', rest)) => match rest.split_first() {
+            Some((literal, rest)) => {
+                !value.is_empty() && value[0] == *literal && like_match(&value[1..], rest)
+            }
+            None => false,
+        },
+        Some(('%', rest)) => (0..=value.len()).any(|skip| like_match(&value[skip..], rest)),
+        Some(('_', rest)) => !value.is_empty() && like_match(&value[1..], rest),
+        Some((c, rest)) => !value.is_empty() && value[0] == *c && like_match(&value[1..], rest),
+    }
+}
+
+/// The result of searching a prover's credentials against a `ProofRequest`:
+/// for every requested attribute and predicate referent, the ids of the
+/// credentials found to satisfy it, in no particular order. A referent with
+/// an empty list has no matching credential in the searched set.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CredentialsForProofRequest {
+    pub requested_attributes: HashMap<String, Vec<String>>,
+    pub requested_predicates: HashMap<String, Vec<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _tags() -> HashMap<String, String> {
+        let mut tags = HashMap::new();
+        tags.insert("schema_id".to_string(), "schema:1".to_string());
+        tags.insert("attr::age::marker".to_string(), "1".to_string());
+        tags.insert("attr::age::value".to_string(), "25".to_string());
+        tags
+    }
+
+    #[test]
+    fn matches_restriction_for_empty_query() {
+        assert!(matches_restriction(&Query::And(vec![]), &_tags()));
+    }
+
+    #[test]
+    fn matches_restriction_for_eq_and_neq() {
+        assert!(matches_restriction(
+            &Query::Eq("schema_id".to_string(), "schema:1".to_string()),
+            &_tags()
+        ));
+        assert!(!matches_restriction(
+            &Query::Eq("schema_id".to_string(), "schema:2".to_string()),
+            &_tags()
+        ));
+        assert!(matches_restriction(
+            &Query::Neq("schema_id".to_string(), "schema:2".to_string()),
+            &_tags()
+        ));
+        assert!(!matches_restriction(
+            &Query::Eq("missing_tag".to_string(), "anything".to_string()),
+            &_tags()
+        ));
+    }
+
+    #[test]
+    fn matches_restriction_for_numeric_comparison() {
+        assert!(matches_restriction(
+            &Query::Gte("attr::age::value".to_string(), "18".to_string()),
+            &_tags()
+        ));
+        assert!(!matches_restriction(
+            &Query::Lt("attr::age::value".to_string(), "18".to_string()),
+            &_tags()
+        ));
+    }
+
+    #[test]
+    fn matches_restriction_for_like() {
+        assert!(matches_restriction(
+            &Query::Like("schema_id".to_string(), "schema:%".to_string()),
+            &_tags()
+        ));
+        assert!(!matches_restriction(
+            &Query::Like("schema_id".to_string(), "other:%".to_string()),
+            &_tags()
+        ));
+    }
+
+    #[test]
+    fn matches_restriction_for_and_or_not() {
+        let query = Query::And(vec![
+            Query::Eq("schema_id".to_string(), "schema:1".to_string()),
+            Query::Eq("attr::age::marker".to_string(), "1".to_string()),
+        ]);
+        assert!(matches_restriction(&query, &_tags()));
+        assert!(!matches_restriction(&Query::Not(Box::new(query)), &_tags()));
+
+        let query = Query::Or(vec![
+            Query::Eq("schema_id".to_string(), "schema:2".to_string()),
+            Query::Eq("schema_id".to_string(), "schema:1".to_string()),
+        ]);
+        assert!(matches_restriction(&query, &_tags()));
+    }
+}
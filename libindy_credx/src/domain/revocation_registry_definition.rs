@@ -6,7 +6,7 @@ use std::collections::HashSet;
 use std::str::FromStr;
 
 use crate::identifiers::cred_def::CredentialDefinitionId;
-use crate::identifiers::rev_reg::RevocationRegistryId;
+use crate::identifiers::rev_reg_def::RevocationRegistryDefinitionId;
 use crate::utils::qualifier::Qualifiable;
 use crate::utils::validation::{Validatable, ValidationError};
 
@@ -80,7 +80,7 @@ pub struct RevocationRegistryDefinitionValuePublicKeys {
 #[derive(Deserialize, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RevocationRegistryDefinitionV1 {
-    pub id: RevocationRegistryId,
+    pub id: RevocationRegistryDefinitionId,
     pub revoc_def_type: RegistryType,
     pub tag: String,
     pub cred_def_id: CredentialDefinitionId,
@@ -119,7 +119,7 @@ pub struct RevocationRegistryDefinitionPrivate {
 
 #[derive(Debug, Deserialize, Serialize, Clone, NamedType)]
 pub struct RevocationRegistryInfo {
-    pub id: RevocationRegistryId,
+    pub id: RevocationRegistryDefinitionId,
     pub curr_id: u32,
     pub used_ids: HashSet<u32>,
 }
@@ -1,5 +1,6 @@
 use ursa::cl::RevocationRegistryDelta as RegistryDelta;
 
+use crate::common::error::prelude::*;
 use crate::utils::validation::Validatable;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,7 +9,7 @@ pub struct RevocationRegistryDeltaV1 {
     pub value: RegistryDelta,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "ver")]
 pub enum RevocationRegistryDelta {
     #[serde(rename = "1.0")]
@@ -16,3 +17,19 @@ pub enum RevocationRegistryDelta {
 }
 
 impl Validatable for RevocationRegistryDelta {}
+
+impl RevocationRegistryDelta {
+    /// Merge a later delta `other`, which must start from the accumulator this
+    /// one ends at, into this one in place, producing the combined delta
+    /// spanning both transitions. Lets a verifier (or an issuer that
+    /// only persists periodic snapshots) fold a run of published deltas down
+    /// to one before applying it, instead of replaying them one at a time.
+    pub fn merge(&mut self, other: &RevocationRegistryDelta) -> IndyResult<()> {
+        match (self, other) {
+            (
+                RevocationRegistryDelta::RevocationRegistryDeltaV1(v1),
+                RevocationRegistryDelta::RevocationRegistryDeltaV1(other_v1),
+            ) => Ok(v1.value.merge(&other_v1.value)?),
+        }
+    }
+}
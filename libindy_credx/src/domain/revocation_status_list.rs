@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+use ursa::cl::issuer::Issuer as CryptoIssuer;
+use ursa::cl::{
+    RevocationRegistry as CryptoRevocationRegistry,
+    RevocationRegistryDelta as CryptoRevocationRegistryDelta, RevocationTailsAccessor,
+};
+
+use crate::common::error::prelude::*;
+use crate::identifiers::rev_reg_def::RevocationRegistryDefinitionId;
+use crate::utils::validation::{Validatable, ValidationError};
+
+use super::revocation_registry_definition::IssuanceType;
+
+/// A snapshot of the full state of a revocation registry at a point in time.
+///
+/// Unlike a `RevocationRegistryDelta`, which only describes a transition between
+/// two accumulator states, a `RevocationStatusList` is self-contained: it carries
+/// the registry's current accumulator together with the revocation status of
+/// every index up to `max_cred_num`, so a verifier can check non-revocation
+/// against a single timestamped snapshot instead of replaying a chain of deltas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationStatusList {
+    pub rev_reg_def_id: RevocationRegistryDefinitionId,
+    pub registry: CryptoRevocationRegistry,
+    /// index `i` (0-based) is `true` when credential `i + 1` is revoked
+    pub revocation_list: Vec<bool>,
+    pub timestamp: u64,
+}
+
+impl RevocationStatusList {
+    /// Seed a fresh snapshot for a newly-created registry: every index starts
+    /// active (not revoked) for `ISSUANCE_BY_DEFAULT`, or not-yet-issued
+    /// (represented the same way as revoked until the credential is signed)
+    /// for `ISSUANCE_ON_DEMAND`.
+    pub fn new(
+        rev_reg_def_id: &RevocationRegistryDefinitionId,
+        registry: CryptoRevocationRegistry,
+        max_cred_num: u32,
+        issuance_type: IssuanceType,
+        timestamp: u64,
+    ) -> Self {
+        let not_active = issuance_type == IssuanceType::ISSUANCE_ON_DEMAND;
+        Self {
+            rev_reg_def_id: rev_reg_def_id.clone(),
+            registry,
+            revocation_list: vec![not_active; max_cred_num as usize],
+            timestamp,
+        }
+    }
+
+    pub fn max_cred_num(&self) -> u32 {
+        self.revocation_list.len() as u32
+    }
+
+    pub fn is_revoked(&self, rev_idx: u32) -> bool {
+        if rev_idx == 0 {
+            return false;
+        }
+        self.revocation_list
+            .get(rev_idx as usize - 1)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Split the current snapshot into the sets of indices that are issued
+    /// (non-revoked) and revoked, as expected by the underlying CL accumulator
+    /// update and witness construction APIs.
+    pub fn issued_and_revoked(&self) -> (HashSet<u32>, HashSet<u32>) {
+        let mut issued = HashSet::new();
+        let mut revoked = HashSet::new();
+        for (idx, is_revoked) in self.revocation_list.iter().enumerate() {
+            let rev_idx = idx as u32 + 1;
+            if *is_revoked {
+                revoked.insert(rev_idx);
+            } else {
+                issued.insert(rev_idx);
+            }
+        }
+        (issued, revoked)
+    }
+
+    /// Flip the bits for the `issued`/`revoked` indices whose status actually
+    /// changes and fold only those transitions into the accumulator, keeping
+    /// the bit-vector and accumulator in lockstep, then advance `timestamp`.
+    /// Returns the delta produced by the accumulator update, for callers that
+    /// still need to publish a `RevocationRegistryDelta` alongside the
+    /// snapshot (e.g. for ledger compatibility).
+    pub fn update(
+        &mut self,
+        issued: HashSet<u32>,
+        revoked: HashSet<u32>,
+        tails_accessor: &impl RevocationTailsAccessor,
+        timestamp: u64,
+    ) -> IndyResult<CryptoRevocationRegistryDelta> {
+        let max_cred_num = self.max_cred_num();
+        let mut newly_issued = HashSet::new();
+        let mut newly_revoked = HashSet::new();
+
+        for idx in issued {
+            if idx == 0 {
+                continue;
+            }
+            let i = (idx - 1) as usize;
+            if self.revocation_list.get(i).copied().unwrap_or(false) {
+                self.revocation_list[i] = false;
+                newly_issued.insert(idx);
+            }
+        }
+        for idx in revoked {
+            if idx == 0 {
+                continue;
+            }
+            let i = (idx - 1) as usize;
+            if !self.revocation_list.get(i).copied().unwrap_or(true) {
+                self.revocation_list[i] = true;
+                newly_revoked.insert(idx);
+            }
+        }
+
+        let delta = CryptoIssuer::update_revocation_registry(
+            &mut self.registry,
+            max_cred_num,
+            newly_issued,
+            newly_revoked,
+            tails_accessor,
+        )?;
+        self.timestamp = timestamp;
+        Ok(delta)
+    }
+}
+
+impl Validatable for RevocationStatusList {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.rev_reg_def_id.validate()?;
+        if self.revocation_list.is_empty() {
+            return Err(invalid!(
+                "RevocationStatusList validation failed: `revocation_list` must not be empty"
+            ));
+        }
+        Ok(())
+    }
+}
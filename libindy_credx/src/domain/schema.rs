@@ -8,6 +8,7 @@ use crate::utils::qualifier::{self, Qualifiable};
 use crate::utils::validation::{Validatable, ValidationError};
 
 pub const MAX_ATTRIBUTES_COUNT: usize = 125;
+pub const MAX_ATTRIBUTE_NAME_LENGTH: usize = 256;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -81,6 +82,48 @@ impl AttributeNames {
     pub fn new() -> Self {
         AttributeNames(HashSet::new())
     }
+
+    /// Trim surrounding whitespace from each attribute name and collect the
+    /// result into a canonical set, so that names differing only by
+    /// surrounding whitespace don't produce divergent schema ids downstream.
+    /// Letter case is left as it is. Rejects names that are empty after trimming,
+    /// contain control characters, exceed `MAX_ATTRIBUTE_NAME_LENGTH` bytes,
+    /// or collide with another name once trimmed.
+    pub fn normalized(&self) -> Result<HashSet<String>, ValidationError> {
+        let mut result = HashSet::new();
+        for name in self.0.iter() {
+            let trimmed = name.trim();
+
+            if trimmed.is_empty() {
+                return Err(invalid!(
+                    "Schema attribute name is empty after trimming whitespace"
+                ));
+            }
+
+            if trimmed.len() > MAX_ATTRIBUTE_NAME_LENGTH {
+                return Err(invalid!(
+                    "Schema attribute name {:?} is longer than {} bytes",
+                    trimmed,
+                    MAX_ATTRIBUTE_NAME_LENGTH
+                ));
+            }
+
+            if trimmed.chars().any(|c| c.is_control()) {
+                return Err(invalid!(
+                    "Schema attribute name {:?} contains control characters",
+                    trimmed
+                ));
+            }
+
+            if !result.insert(trimmed.to_owned()) {
+                return Err(invalid!(
+                    "Schema attribute name {:?} is a duplicate of another attribute name once trimmed",
+                    trimmed
+                ));
+            }
+        }
+        Ok(result)
+    }
 }
 
 impl From<HashSet<String>> for AttributeNames {
@@ -108,6 +151,9 @@ impl Validatable for AttributeNames {
                 MAX_ATTRIBUTES_COUNT
             ));
         }
+
+        self.normalized()?;
+
         Ok(())
     }
 }
@@ -380,4 +426,58 @@ mod tests {
             schema.validate().unwrap_err();
         }
     }
+
+    mod normalized {
+        use super::*;
+
+        #[test]
+        fn test_normalized_trims_whitespace() {
+            let attrs = AttributeNames::from(
+                vec![" aaa ".to_string(), "bbb".to_string()]
+                    .into_iter()
+                    .collect::<HashSet<_>>(),
+            );
+            let normalized = attrs.normalized().unwrap();
+            assert!(normalized.contains("aaa"));
+            assert!(normalized.contains("bbb"));
+        }
+
+        #[test]
+        fn test_normalized_rejects_empty_after_trim() {
+            let attrs = AttributeNames::from(
+                vec!["   ".to_string()].into_iter().collect::<HashSet<_>>(),
+            );
+            attrs.normalized().unwrap_err();
+        }
+
+        #[test]
+        fn test_normalized_rejects_duplicate_after_trim() {
+            let attrs = AttributeNames::from(
+                vec!["aaa".to_string(), " aaa".to_string()]
+                    .into_iter()
+                    .collect::<HashSet<_>>(),
+            );
+            attrs.normalized().unwrap_err();
+        }
+
+        #[test]
+        fn test_normalized_rejects_control_characters() {
+            let attrs = AttributeNames::from(
+                vec!["aaa\u{0007}".to_string()]
+                    .into_iter()
+                    .collect::<HashSet<_>>(),
+            );
+            attrs.normalized().unwrap_err();
+        }
+
+        #[test]
+        fn test_normalized_rejects_overlong_name() {
+            let attrs = AttributeNames::from(
+                vec!["a".repeat(MAX_ATTRIBUTE_NAME_LENGTH + 1)]
+                    .into_iter()
+                    .collect::<HashSet<_>>(),
+            );
+            attrs.normalized().unwrap_err();
+        }
+    }
 }
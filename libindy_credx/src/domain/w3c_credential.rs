@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use ursa::hash::sha2::{Digest, Sha256};
+
+use crate::common::error::IndyResult;
+use crate::domain::credential::Credential;
+use crate::identifiers::rev_reg_def::RevocationRegistryDefinitionId;
+
+/// A W3C VCDM `credentialStatus` entry for an AnonCreds revocation registry:
+/// just enough for a verifier to know which accumulator to check, since the
+/// per-credential revocation index lives inside the opaque CL signature and
+/// isn't recoverable from the `Credential` alone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct W3CCredentialStatus {
+    pub id: RevocationRegistryDefinitionId,
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+/// A W3C Verifiable Credential Data Model document projected from an
+/// Indy/AnonCreds `Credential`. This is an interop view only — the
+/// cryptographic guarantee of issuance remains the CL signature on the
+/// original `Credential`, not anything carried by this document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct W3CCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    #[serde(rename = "type")]
+    pub type_: Vec<String>,
+    pub issuer: String,
+    pub credential_subject: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<W3CCredentialStatus>,
+}
+
+/// Output encodings supported by [`Credential::to_w3c_vc`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum W3CVcFormat {
+    JsonLd,
+    Jwt,
+}
+
+impl Credential {
+    /// Project this credential's decoded raw attribute values into a W3C
+    /// VCDM document, deriving `credentialStatus` from the revocation
+    /// registry id when the credential was issued against one.
+    pub fn to_w3c(&self, issuer_did: &str, subject_did: &str) -> W3CCredential {
+        let mut credential_subject = HashMap::new();
+        credential_subject.insert("id".to_string(), subject_did.to_string());
+        for (name, value) in self.values.0.iter() {
+            credential_subject.insert(name.clone(), value.raw.clone());
+        }
+        let credential_status = self.rev_reg_id.clone().map(|id| W3CCredentialStatus {
+            id,
+            type_: "AnonCredsAccumulator".to_string(),
+        });
+        W3CCredential {
+            context: vec![
+                "https://www.w3.org/2018/credentials/v1".to_string(),
+                "https://www.w3.org/2018/credentials/examples/v1".to_string(),
+            ],
+            type_: vec!["VerifiableCredential".to_string()],
+            issuer: issuer_did.to_string(),
+            credential_subject,
+            credential_status,
+        }
+    }
+
+    /// Encode this credential as a W3C Verifiable Credential, either as a
+    /// plain JSON-LD document or as a compact JWT VC whose `vc` claim holds
+    /// that same document with `iss`/`sub`/`jti` populated. The JWT is
+    /// unsigned (`alg: none`): it exists so a holder can hand this credential
+    /// to a verifier that only speaks the `vc` JWT convention, not to
+    /// replace the CL signature as the proof of issuance.
+    pub fn to_w3c_vc(
+        &self,
+        issuer_did: &str,
+        subject_did: &str,
+        format: W3CVcFormat,
+    ) -> IndyResult<String> {
+        let document = self.to_w3c(issuer_did, subject_did);
+        match format {
+            W3CVcFormat::JsonLd => Ok(serde_json::to_string(&document)?),
+            W3CVcFormat::Jwt => {
+                let jti = format!("urn:credx:{}", jti_digest(issuer_did, subject_did, &document)?);
+                let header = serde_json::json!({"alg": "none", "typ": "JWT"});
+                let payload = serde_json::json!({
+                    "iss": issuer_did,
+                    "sub": subject_did,
+                    "jti": jti,
+                    "vc": document,
+                });
+                Ok(format!(
+                    "{}.{}.",
+                    base64url_encode(&serde_json::to_vec(&header)?),
+                    base64url_encode(&serde_json::to_vec(&payload)?),
+                ))
+            }
+        }
+    }
+}
+
+/// Deterministic credential identifier for the JWT's `jti` claim: this crate
+/// has no DID-keyed signer to mint a random one from, so a hash of the
+/// credential's own content is used instead, matching the repo's existing
+/// preference (see `AttributeValues::encode`) for SHA-256 over pulling in a
+/// UUID dependency.
+fn jti_digest(issuer_did: &str, subject_did: &str, document: &W3CCredential) -> IndyResult<String> {
+    let mut hasher = Sha256::default();
+    hasher.input(issuer_did.as_bytes());
+    hasher.input(subject_did.as_bytes());
+    hasher.input(&serde_json::to_vec(document)?);
+    Ok(hex_encode(&hasher.result()))
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn base64url_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+        let n = ((b0 as u32) << 16) | ((b1 as u32) << 8) | (b2 as u32);
+        out.push(ALPHABET[((n >> 18) & 0x3F) as usize] as char);
+        out.push(ALPHABET[((n >> 12) & 0x3F) as usize] as char);
+        if chunk.len() > 1 {
+            out.push(ALPHABET[((n >> 6) & 0x3F) as usize] as char);
+        }
+        if chunk.len() > 2 {
+            out.push(ALPHABET[(n & 0x3F) as usize] as char);
+        }
+    }
+    out
+}
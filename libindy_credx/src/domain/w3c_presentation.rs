@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use ursa::cl::{Nonce, Proof as CryptoProof};
+
+use crate::common::error::prelude::*;
+use crate::domain::credential_definition::CredentialDefinition;
+use crate::domain::proof::{Identifier, Proof, RequestedProof};
+use crate::domain::proof_request::ProofRequestPayload;
+use crate::domain::schema::Schema;
+use crate::identifiers::cred_def::CredentialDefinitionId;
+use crate::identifiers::rev_reg_def::RevocationRegistryDefinitionId;
+use crate::identifiers::schema::SchemaId;
+
+/// A single attribute's contribution to a W3C AnonCreds presentation:
+/// either a revealed plaintext value, or the boolean outcome of a
+/// predicate that was proved (but not revealed) against it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CredentialAttributeValue {
+    Attribute(String),
+    Predicate(bool),
+}
+
+/// The portion of a W3C presentation contributed by a single credential's
+/// sub-proof: which schema/cred-def/revocation-registry it was issued
+/// against, the non-revocation timestamp it was proved current as of (if
+/// any), and the revealed attributes / predicate outcomes it attests to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct W3CCredentialPresentation {
+    pub schema_id: SchemaId,
+    pub cred_def_id: CredentialDefinitionId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg_id: Option<RevocationRegistryDefinitionId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    pub attributes: HashMap<String, CredentialAttributeValue>,
+}
+
+/// A W3C-format presentation: the underlying CL proof plus the per-credential
+/// attribute/predicate document it attests to. This is an alternative wire
+/// format to the legacy `Proof`/`RequestedProof` pair consumed directly by
+/// `Verifier::verify_proof`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct W3CPresentation {
+    pub proof: CryptoProof,
+    pub credentials: Vec<W3CCredentialPresentation>,
+}
+
+/// A single sub-proof wrapped as a W3C Verifiable Credential entry inside a
+/// `VerifiablePresentation`'s `verifiableCredential` array. `credential_subject`
+/// mirrors `W3CCredentialPresentation::attributes`: revealed attribute values
+/// keyed by `attr_common_view`-normalized name, and predicate outcomes as
+/// derived boolean claims under the predicate's own attribute name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredentialPresentation {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    #[serde(rename = "type")]
+    pub type_: Vec<String>,
+    pub credential_subject: HashMap<String, CredentialAttributeValue>,
+    pub schema_id: SchemaId,
+    pub cred_def_id: CredentialDefinitionId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg_id: Option<RevocationRegistryDefinitionId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+}
+
+/// The anoncreds-specific `proof` object attached to a `VerifiablePresentation`:
+/// the raw CL proof, the request nonce it was built against, and the full
+/// `requested_proof` it was built from. `requested_proof` is what makes
+/// [`Proof::from_w3c_presentation`] exact: it already maps every referent to
+/// its sub-proof index and (for revealed attributes) raw/encoded value, so
+/// recovery reads it back directly instead of re-deriving that mapping by
+/// matching attribute names against `verifiableCredential` entries — a
+/// lookup that can't disambiguate two credentials revealing the same name,
+/// and has no way to represent a self-attested or unrevealed referent at
+/// all. `verifiableCredential[].credentialSubject` stays purely for
+/// human/JSON-LD readability; it is not read back by `from_w3c_presentation`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonCredsPresentationProof {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub cl_proof: CryptoProof,
+    pub nonce: Nonce,
+    pub requested_proof: RequestedProof,
+}
+
+/// A full W3C `VerifiablePresentation` wrapping an AnonCreds `Proof`: every
+/// sub-proof becomes a `verifiableCredential` entry, and the underlying CL
+/// proof material, request nonce, and source `RequestedProof` ride along
+/// under an anoncreds-specific `proof` object so [`Proof::from_w3c_presentation`]
+/// can recover the native `Proof` exactly, including self-attested and
+/// unrevealed referents. This is an interop view only — the cryptographic
+/// guarantee remains the CL proof, not anything else carried by this
+/// document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiablePresentation {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    #[serde(rename = "type")]
+    pub type_: Vec<String>,
+    pub verifiable_credential: Vec<VerifiableCredentialPresentation>,
+    pub proof: AnonCredsPresentationProof,
+}
+
+fn attr_common_view(attr: &str) -> String {
+    attr.replace(" ", "").to_lowercase()
+}
+
+impl Proof {
+    /// Projects this proof into a W3C `VerifiablePresentation` document: one
+    /// `verifiableCredential` entry per sub-proof, with its revealed
+    /// attributes and proved predicates carried under `credentialSubject`,
+    /// and the CL proof plus the request nonce attached under the
+    /// anoncreds-specific `proof` object. `schemas`/`cred_defs` are checked
+    /// against each sub-proof's identifiers the same way `Verifier::verify_proof`
+    /// does, so a presentation can't be built referencing a schema or
+    /// credential definition the caller didn't actually supply.
+    pub fn to_w3c_presentation(
+        &self,
+        proof_req: &ProofRequestPayload,
+        schemas: &HashMap<SchemaId, &Schema>,
+        cred_defs: &HashMap<CredentialDefinitionId, &CredentialDefinition>,
+    ) -> IndyResult<VerifiablePresentation> {
+        let mut credential_subjects: HashMap<usize, HashMap<String, CredentialAttributeValue>> =
+            HashMap::new();
+
+        for (referent, info) in proof_req.requested_attributes.iter() {
+            if let Some(name) = &info.name {
+                if let Some(attr) = self.requested_proof.revealed_attrs.get(referent) {
+                    credential_subjects
+                        .entry(attr.sub_proof_index as usize)
+                        .or_insert_with(HashMap::new)
+                        .insert(
+                            attr_common_view(name),
+                            CredentialAttributeValue::Attribute(attr.raw.clone()),
+                        );
+                }
+            } else if let Some(names) = &info.names {
+                if let Some(group) = self.requested_proof.revealed_attr_groups.get(referent) {
+                    let subject = credential_subjects
+                        .entry(group.sub_proof_index as usize)
+                        .or_insert_with(HashMap::new);
+                    for name in names {
+                        if let Some(value) = group.values.get(name) {
+                            subject.insert(
+                                attr_common_view(name),
+                                CredentialAttributeValue::Attribute(value.raw.clone()),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        for (referent, info) in proof_req.requested_predicates.iter() {
+            if let Some(pred) = self.requested_proof.predicates.get(referent) {
+                credential_subjects
+                    .entry(pred.sub_proof_index as usize)
+                    .or_insert_with(HashMap::new)
+                    .insert(
+                        attr_common_view(&info.name),
+                        CredentialAttributeValue::Predicate(true),
+                    );
+            }
+        }
+
+        let mut verifiable_credential = Vec::with_capacity(self.identifiers.len());
+        for (sub_proof_index, identifier) in self.identifiers.iter().enumerate() {
+            schemas.get(&identifier.schema_id).ok_or_else(|| {
+                input_err(format!("Schema not found for id: {:?}", identifier.schema_id))
+            })?;
+            cred_defs.get(&identifier.cred_def_id).ok_or_else(|| {
+                input_err(format!(
+                    "CredentialDefinition not found for id: {:?}",
+                    identifier.cred_def_id
+                ))
+            })?;
+
+            verifiable_credential.push(VerifiableCredentialPresentation {
+                context: vec![
+                    "https://www.w3.org/2018/credentials/v1".to_string(),
+                    "https://www.w3.org/2018/credentials/examples/v1".to_string(),
+                ],
+                type_: vec!["VerifiableCredential".to_string()],
+                credential_subject: credential_subjects
+                    .remove(&sub_proof_index)
+                    .unwrap_or_default(),
+                schema_id: identifier.schema_id.clone(),
+                cred_def_id: identifier.cred_def_id.clone(),
+                rev_reg_id: identifier.rev_reg_id.clone(),
+                timestamp: identifier.timestamp,
+            });
+        }
+
+        Ok(VerifiablePresentation {
+            context: vec!["https://www.w3.org/2018/credentials/v1".to_string()],
+            type_: vec!["VerifiablePresentation".to_string()],
+            verifiable_credential,
+            proof: AnonCredsPresentationProof {
+                type_: "AnonCredsPresentationProof2023".to_string(),
+                cl_proof: self.proof.clone(),
+                nonce: proof_req.nonce.clone(),
+                requested_proof: self.requested_proof.clone(),
+            },
+        })
+    }
+
+    /// Recovers the native `Proof` a `VerifiablePresentation` was built from,
+    /// the exact inverse of [`Proof::to_w3c_presentation`], so a verifier
+    /// that only has the W3C document can still run `Verifier::verify_proof`
+    /// against it. Unlike `verifiableCredential[].credentialSubject` (which
+    /// is keyed by attribute name and so can't disambiguate two credentials
+    /// revealing the same name), `proof.requestedProof` already carries the
+    /// exact referent-to-sub-proof-index mapping `to_w3c_presentation` built
+    /// it from, so recovery is a direct clone rather than a lookup.
+    pub fn from_w3c_presentation(presentation: &VerifiablePresentation) -> IndyResult<Proof> {
+        let identifiers = presentation
+            .verifiable_credential
+            .iter()
+            .map(|cred| Identifier {
+                schema_id: cred.schema_id.clone(),
+                cred_def_id: cred.cred_def_id.clone(),
+                rev_reg_id: cred.rev_reg_id.clone(),
+                timestamp: cred.timestamp,
+            })
+            .collect();
+
+        Ok(Proof {
+            proof: presentation.proof.cl_proof.clone(),
+            requested_proof: presentation.proof.requested_proof.clone(),
+            identifiers,
+        })
+    }
+}
@@ -0,0 +1,120 @@
+use crate::common::did::DidValue;
+use crate::identifiers::schema::SchemaId;
+use crate::utils::qualifier::{self, Qualifiable};
+use crate::utils::validation::{Validatable, ValidationError};
+
+const DELIMITER: char = ':';
+
+qualifiable_type!(CredentialDefinitionId);
+
+impl Qualifiable for CredentialDefinitionId {
+    fn prefix() -> &'static str {
+        Self::PREFIX
+    }
+
+    fn combine(method: Option<&str>, entity: &str) -> Self {
+        let id = Self(entity.to_owned());
+        match id.parts() {
+            Some((_, did, sig_type, schema_id, tag)) => Self::from(qualifier::combine(
+                Self::PREFIX,
+                method,
+                Self::new(
+                    &did.default_method(method),
+                    &schema_id.default_method(method),
+                    &sig_type,
+                    &tag,
+                )
+                .as_str(),
+            )),
+            None => id,
+        }
+    }
+
+    fn to_unqualified(&self) -> Self {
+        match self.parts() {
+            Some((method, did, sig_type, schema_id, tag)) => {
+                let did = if let Some(method) = method {
+                    did.remove_method(method)
+                } else {
+                    did
+                };
+                Self::new(&did, &schema_id.to_unqualified(), &sig_type, &tag)
+            }
+            None => self.clone(),
+        }
+    }
+}
+
+impl CredentialDefinitionId {
+    pub const PREFIX: &'static str = "creddef";
+    pub const MARKER: &'static str = "3";
+
+    pub fn new(origin_did: &DidValue, schema_id: &SchemaId, signature_type: &str, tag: &str) -> Self {
+        let id = format!(
+            "{}{}{}{}{}{}{}{}{}",
+            origin_did.0,
+            DELIMITER,
+            Self::MARKER,
+            DELIMITER,
+            signature_type,
+            DELIMITER,
+            schema_id.0,
+            DELIMITER,
+            tag
+        );
+        Self::from(qualifier::combine(
+            Self::PREFIX,
+            origin_did.get_method(),
+            id.as_str(),
+        ))
+    }
+
+    pub fn parts(&self) -> Option<(Option<&str>, DidValue, String, SchemaId, String)> {
+        let parts = self.0.split_terminator(DELIMITER).collect::<Vec<&str>>();
+
+        if parts.len() == 1 {
+            return None;
+        }
+
+        if parts.len() == 5 {
+            // NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag
+            let did = parts[0].to_string();
+            let sig_type = parts[2].to_string();
+            let schema_id = parts[3].to_string();
+            let tag = parts[4].to_string();
+            return Some((None, DidValue(did), sig_type, SchemaId(schema_id), tag));
+        }
+
+        if parts.len() >= 9 {
+            // creddef:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:3:CL:<schema_id>:tag
+            let method = parts[1];
+            let did = parts[2..5].join(&DELIMITER.to_string());
+            let sig_type = parts[6].to_string();
+            let schema_id = parts[7..parts.len() - 1].join(&DELIMITER.to_string());
+            let tag = parts[parts.len() - 1].to_string();
+            return Some((
+                Some(method),
+                DidValue(did),
+                sig_type,
+                SchemaId(schema_id),
+                tag,
+            ));
+        }
+
+        None
+    }
+
+    pub fn issuer_did(&self) -> Option<DidValue> {
+        self.parts().map(|(_, did, ..)| did)
+    }
+}
+
+impl Validatable for CredentialDefinitionId {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.parts().ok_or(invalid!(
+            "CredentialDefinitionId validation failed: {:?}, doesn't match pattern",
+            self.0
+        ))?;
+        Ok(())
+    }
+}
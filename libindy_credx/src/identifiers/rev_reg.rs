@@ -0,0 +1,134 @@
+use crate::common::did::DidValue;
+use crate::identifiers::cred_def::CredentialDefinitionId;
+use crate::utils::qualifier::{self, Qualifiable};
+use crate::utils::validation::{Validatable, ValidationError};
+
+const DELIMITER: char = ':';
+
+qualifiable_type!(RevocationRegistryId);
+
+impl Qualifiable for RevocationRegistryId {
+    fn prefix() -> &'static str {
+        Self::PREFIX
+    }
+
+    fn combine(method: Option<&str>, entity: &str) -> Self {
+        let id = Self(entity.to_owned());
+        match id.parts() {
+            Some((_, did, cred_def_id, rev_reg_type, tag)) => Self::from(qualifier::combine(
+                Self::PREFIX,
+                method,
+                Self::new(
+                    &did.default_method(method),
+                    &cred_def_id.default_method(method),
+                    &rev_reg_type,
+                    &tag,
+                )
+                .as_str(),
+            )),
+            None => id,
+        }
+    }
+
+    fn to_unqualified(&self) -> Self {
+        match self.parts() {
+            Some((method, did, cred_def_id, rev_reg_type, tag)) => {
+                let did = if let Some(method) = method {
+                    did.remove_method(method)
+                } else {
+                    did
+                };
+                Self::new(&did, &cred_def_id.to_unqualified(), &rev_reg_type, &tag)
+            }
+            None => self.clone(),
+        }
+    }
+}
+
+impl RevocationRegistryId {
+    pub const PREFIX: &'static str = "revreg";
+    pub const MARKER: &'static str = "4";
+
+    pub fn new(
+        origin_did: &DidValue,
+        cred_def_id: &CredentialDefinitionId,
+        rev_reg_type: &str,
+        tag: &str,
+    ) -> Self {
+        let id = format!(
+            "{}{}{}{}{}{}{}{}{}",
+            origin_did.0,
+            DELIMITER,
+            Self::MARKER,
+            DELIMITER,
+            cred_def_id.0,
+            DELIMITER,
+            rev_reg_type,
+            DELIMITER,
+            tag
+        );
+        Self::from(qualifier::combine(
+            Self::PREFIX,
+            origin_did.get_method(),
+            id.as_str(),
+        ))
+    }
+
+    /// Everything between the origin DID and the trailing `rev_reg_type:tag`
+    /// belongs to the embedded `CredentialDefinitionId`, which may itself
+    /// contain any number of delimiters, so it is extracted by position
+    /// rather than by a fixed part count (mirrors `RevocationRegistryDefinitionId::parts`).
+    pub fn parts(
+        &self,
+    ) -> Option<(Option<&str>, DidValue, CredentialDefinitionId, String, String)> {
+        let parts = self.0.split_terminator(DELIMITER).collect::<Vec<&str>>();
+
+        if parts.len() < 5 {
+            return None;
+        }
+
+        if let Some(method) = self.get_method() {
+            // revreg:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:4:<cred_def_id>:CL_ACCUM:tag
+            if parts.len() < 8 || parts.get(5) != Some(&Self::MARKER) {
+                return None;
+            }
+            let did = parts[2..5].join(&DELIMITER.to_string());
+            let tag = parts[parts.len() - 1].to_string();
+            let rev_reg_type = parts[parts.len() - 2].to_string();
+            let cred_def_id = parts[6..parts.len() - 2].join(&DELIMITER.to_string());
+            Some((
+                Some(method),
+                DidValue(did),
+                CredentialDefinitionId(cred_def_id),
+                rev_reg_type,
+                tag,
+            ))
+        } else {
+            // NcYxiDXkpYi6ov5FcYDi1e:4:NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag:CL_ACCUM:tag2
+            if parts.get(1) != Some(&Self::MARKER) {
+                return None;
+            }
+            let did = parts[0].to_string();
+            let tag = parts[parts.len() - 1].to_string();
+            let rev_reg_type = parts[parts.len() - 2].to_string();
+            let cred_def_id = parts[2..parts.len() - 2].join(&DELIMITER.to_string());
+            Some((
+                None,
+                DidValue(did),
+                CredentialDefinitionId(cred_def_id),
+                rev_reg_type,
+                tag,
+            ))
+        }
+    }
+}
+
+impl Validatable for RevocationRegistryId {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.parts().ok_or(invalid!(
+            "RevocationRegistryId validation failed: {:?}, doesn't match pattern",
+            self.0
+        ))?;
+        Ok(())
+    }
+}
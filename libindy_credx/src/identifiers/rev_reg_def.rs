@@ -0,0 +1,145 @@
+use crate::common::did::DidValue;
+use crate::identifiers::cred_def::CredentialDefinitionId;
+use crate::identifiers::rev_reg::RevocationRegistryId;
+use crate::utils::qualifier::{self, Qualifiable};
+use crate::utils::validation::{Validatable, ValidationError};
+
+const DELIMITER: char = ':';
+
+qualifiable_type!(RevocationRegistryDefinitionId);
+
+impl Qualifiable for RevocationRegistryDefinitionId {
+    fn prefix() -> &'static str {
+        Self::PREFIX
+    }
+
+    fn combine(method: Option<&str>, entity: &str) -> Self {
+        let id = Self(entity.to_owned());
+        match id.parts() {
+            Some((_, did, cred_def_id, revoc_def_type, tag)) => Self::from(qualifier::combine(
+                Self::PREFIX,
+                method,
+                Self::new(
+                    &did.default_method(method),
+                    &cred_def_id.default_method(method),
+                    &revoc_def_type,
+                    &tag,
+                )
+                .as_str(),
+            )),
+            None => id,
+        }
+    }
+
+    fn to_unqualified(&self) -> Self {
+        match self.parts() {
+            Some((method, did, cred_def_id, revoc_def_type, tag)) => {
+                let did = if let Some(method) = method {
+                    did.remove_method(method)
+                } else {
+                    did
+                };
+                Self::new(&did, &cred_def_id.to_unqualified(), &revoc_def_type, &tag)
+            }
+            None => self.clone(),
+        }
+    }
+}
+
+impl RevocationRegistryDefinitionId {
+    pub const PREFIX: &'static str = "revreg";
+    pub const MARKER: &'static str = "4";
+
+    pub fn new(
+        origin_did: &DidValue,
+        cred_def_id: &CredentialDefinitionId,
+        revoc_def_type: &str,
+        tag: &str,
+    ) -> Self {
+        let id = format!(
+            "{}{}{}{}{}{}{}{}{}",
+            origin_did.0,
+            DELIMITER,
+            Self::MARKER,
+            DELIMITER,
+            cred_def_id.0,
+            DELIMITER,
+            revoc_def_type,
+            DELIMITER,
+            tag
+        );
+        Self::from(qualifier::combine(
+            Self::PREFIX,
+            origin_did.get_method(),
+            id.as_str(),
+        ))
+    }
+
+    /// Everything between the origin DID and the trailing `revoc_def_type:tag`
+    /// belongs to the embedded `CredentialDefinitionId`, which may itself
+    /// contain any number of delimiters, so it is extracted by position
+    /// rather than by a fixed part count (mirrors `CredentialDefinitionId::parts`).
+    pub fn parts(
+        &self,
+    ) -> Option<(Option<&str>, DidValue, CredentialDefinitionId, String, String)> {
+        let parts = self.0.split_terminator(DELIMITER).collect::<Vec<&str>>();
+
+        if parts.len() < 5 {
+            return None;
+        }
+
+        if let Some(method) = self.get_method() {
+            // revreg:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:4:<cred_def_id>:CL_ACCUM:tag
+            if parts.len() < 8 || parts.get(5) != Some(&Self::MARKER) {
+                return None;
+            }
+            let did = parts[2..5].join(&DELIMITER.to_string());
+            let tag = parts[parts.len() - 1].to_string();
+            let revoc_def_type = parts[parts.len() - 2].to_string();
+            let cred_def_id = parts[6..parts.len() - 2].join(&DELIMITER.to_string());
+            Some((
+                Some(method),
+                DidValue(did),
+                CredentialDefinitionId(cred_def_id),
+                revoc_def_type,
+                tag,
+            ))
+        } else {
+            // NcYxiDXkpYi6ov5FcYDi1e:4:NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag:CL_ACCUM:tag2
+            if parts.get(1) != Some(&Self::MARKER) {
+                return None;
+            }
+            let did = parts[0].to_string();
+            let tag = parts[parts.len() - 1].to_string();
+            let revoc_def_type = parts[parts.len() - 2].to_string();
+            let cred_def_id = parts[2..parts.len() - 2].join(&DELIMITER.to_string());
+            Some((
+                None,
+                DidValue(did),
+                CredentialDefinitionId(cred_def_id),
+                revoc_def_type,
+                tag,
+            ))
+        }
+    }
+}
+
+impl Validatable for RevocationRegistryDefinitionId {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.parts().ok_or(invalid!(
+            "RevocationRegistryDefinitionId validation failed: {:?}, doesn't match pattern",
+            self.0
+        ))?;
+        Ok(())
+    }
+}
+
+/// Definitions are identified by `RevocationRegistryDefinitionId`; accumulator
+/// state/ledger-entry lookups still use the plain `RevocationRegistryId`. The two share the same underlying string, so this
+/// conversion lets definition-side code hand its id to entry-side APIs
+/// (e.g. tails storage) without re-deriving it.
+impl From<&RevocationRegistryDefinitionId> for RevocationRegistryId {
+    fn from(id: &RevocationRegistryDefinitionId) -> Self {
+        RevocationRegistryId(id.0.clone())
+    }
+}
@@ -0,0 +1 @@
+pub use crate::domain::schema::SchemaId;
@@ -5,8 +5,10 @@ use ursa::cl::{
 
 use crate::common::error::prelude::*;
 
-use crate::domain::credential::AttributeValues;
-use crate::domain::proof_request::{AttributeInfo, NonRevocedInterval, PredicateInfo};
+use crate::domain::credential::{AttributeValues, Credential};
+use crate::domain::proof_request::{
+    AttributeInfo, NonRevocedInterval, PredicateInfo, PredicateTypes,
+};
 
 use std::collections::{HashMap, HashSet};
 
@@ -113,6 +115,62 @@ pub fn build_sub_proof_request(
     Ok(res)
 }
 
+/// Checks whether a credential attribute's raw value satisfies a requested
+/// predicate bound, the same way the CL predicate proof itself would: by
+/// comparing the value as a signed 32-bit integer, since that is the only
+/// numeric encoding `AttributeValues::encode` (and the predicate proof
+/// machinery) understands. A value that doesn't parse as one can never
+/// satisfy a predicate.
+pub fn satisfies_predicate(raw: &str, predicate: &PredicateInfo) -> bool {
+    let value = match raw.parse::<i32>() {
+        Ok(value) => value,
+        Err(_) => return false,
+    };
+    match predicate.p_type {
+        PredicateTypes::GE => value >= predicate.p_value,
+        PredicateTypes::GT => value > predicate.p_value,
+        PredicateTypes::LE => value <= predicate.p_value,
+        PredicateTypes::LT => value < predicate.p_value,
+    }
+}
+
+/// Builds the anoncreds restriction tag set for a credential: the fixed
+/// `schema_id`/`schema_name`/`schema_version`/`schema_issuer_did`/
+/// `issuer_did`/`cred_def_id`/`rev_reg_id` identifiers, plus a per-attribute
+/// `attr::<name>::marker`/`attr::<name>::value` pair for every attribute the
+/// credential carries. Feed this into
+/// `domain::proof_request::matches_restriction` to evaluate a referent's
+/// `restrictions` query against the credential.
+pub fn build_credential_tags(credential: &Credential) -> HashMap<String, String> {
+    let mut tags = HashMap::new();
+
+    tags.insert("schema_id".to_string(), credential.schema_id.0.clone());
+    if let Some((_, schema_issuer_did, schema_name, schema_version)) =
+        credential.schema_id.parts()
+    {
+        tags.insert("schema_issuer_did".to_string(), schema_issuer_did.0);
+        tags.insert("schema_name".to_string(), schema_name);
+        tags.insert("schema_version".to_string(), schema_version);
+    }
+
+    tags.insert("cred_def_id".to_string(), credential.cred_def_id.0.clone());
+    if let Some(issuer_did) = credential.cred_def_id.issuer_did() {
+        tags.insert("issuer_did".to_string(), issuer_did.0);
+    }
+
+    if let Some(rev_reg_id) = &credential.rev_reg_id {
+        tags.insert("rev_reg_id".to_string(), rev_reg_id.0.clone());
+    }
+
+    for (name, values) in credential.values.0.iter() {
+        let name = attr_common_view(name);
+        tags.insert(format!("attr::{}::marker", name), "1".to_string());
+        tags.insert(format!("attr::{}::value", name), values.raw.clone());
+    }
+
+    tags
+}
+
 pub fn get_non_revoc_interval(
     global_interval: &Option<NonRevocedInterval>,
     local_interval: &Option<NonRevocedInterval>,
@@ -160,4 +218,25 @@ mod tests {
         let res = get_non_revoc_interval(&None, &None);
         assert_eq!(None, res);
     }
+
+    fn _predicate(p_type: PredicateTypes, p_value: i32) -> PredicateInfo {
+        PredicateInfo {
+            name: "age".to_string(),
+            p_type,
+            p_value,
+            restrictions: None,
+            non_revoked: None,
+        }
+    }
+
+    #[test]
+    fn satisfies_predicate_for_matching_ge() {
+        assert!(satisfies_predicate("18", &_predicate(PredicateTypes::GE, 18)));
+        assert!(!satisfies_predicate("17", &_predicate(PredicateTypes::GE, 18)));
+    }
+
+    #[test]
+    fn satisfies_predicate_for_non_numeric_raw() {
+        assert!(!satisfies_predicate("not-a-number", &_predicate(PredicateTypes::GE, 18)));
+    }
 }
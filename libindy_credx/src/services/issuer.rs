@@ -18,15 +18,17 @@ use crate::domain::revocation_registry_definition::{
 use crate::domain::revocation_registry_delta::{
     RevocationRegistryDelta, RevocationRegistryDeltaV1,
 };
+use crate::domain::revocation_status_list::RevocationStatusList;
 use crate::domain::schema::{AttributeNames, Schema, SchemaV1};
 use crate::identifiers::cred_def::CredentialDefinitionId;
 use crate::identifiers::rev_reg::RevocationRegistryId;
+use crate::identifiers::rev_reg_def::RevocationRegistryDefinitionId;
 use crate::identifiers::schema::SchemaId;
 use crate::services::helpers::*;
 use crate::utils::qualifier::Qualifiable;
 use crate::utils::validation::Validatable;
 
-use super::tails::{TailsFileReader, TailsReader, TailsWriter};
+use super::tails::{BlobStorage, TailsReader, TailsWriter};
 use super::{
     new_nonce, CredentialKeyCorrectnessProof, CredentialPrivateKey, CryptoIssuer,
     CryptoRevocationRegistryDelta, RevocationKeyPrivate, Witness,
@@ -50,7 +52,7 @@ impl Issuer {
             id: schema_id,
             name: schema_name.to_string(),
             version: schema_version.to_string(),
-            attr_names,
+            attr_names: AttributeNames::from(attr_names.normalized()?),
             seq_no: None,
         };
         Ok(Schema::SchemaV1(schema))
@@ -148,12 +150,12 @@ impl Issuer {
         ))
     }
 
-    pub fn make_revocation_registry_id(
+    pub fn make_revocation_registry_definition_id(
         origin_did: &DidValue,
         cred_def: &CredentialDefinition,
         tag: &str,
         rev_reg_type: RegistryType,
-    ) -> IndyResult<RevocationRegistryId> {
+    ) -> IndyResult<RevocationRegistryDefinitionId> {
         let cred_def = match cred_def {
             CredentialDefinition::CredentialDefinitionV1(c) => c,
         };
@@ -168,7 +170,7 @@ impl Issuer {
             _ => origin_did,
         };
 
-        Ok(RevocationRegistryId::new(
+        Ok(RevocationRegistryDefinitionId::new(
             &origin_did,
             &cred_def.id,
             &rev_reg_type.to_str(),
@@ -176,27 +178,27 @@ impl Issuer {
         ))
     }
 
-    pub fn new_revocation_registry<TW>(
+    pub fn new_revocation_registry<B>(
         origin_did: &DidValue,
         cred_def: &CredentialDefinition,
         tag: &str,
         rev_reg_type: RegistryType,
         issuance_type: IssuanceType,
         max_cred_num: u32,
-        tails_writer: &mut TW,
+        tails_writer: &mut TailsWriter<B>,
     ) -> IndyResult<(
         RevocationRegistryDefinition,
         RevocationRegistry,
         RevocationKeyPrivate,
     )>
     where
-        TW: TailsWriter,
+        B: BlobStorage,
     {
         trace!("new_revocation_registry >>> origin_did: {:?}, cred_def: {:?}, tag: {:?}, max_cred_num: {:?}, rev_reg_type: {:?}, issuance_type: {:?}",
                origin_did, cred_def, tag, max_cred_num, rev_reg_type, issuance_type);
 
         let rev_reg_id =
-            Self::make_revocation_registry_id(origin_did, cred_def, tag, rev_reg_type)?;
+            Self::make_revocation_registry_definition_id(origin_did, cred_def, tag, rev_reg_type)?;
 
         let cred_def = match cred_def {
             CredentialDefinition::CredentialDefinitionV1(c) => c,
@@ -213,6 +215,12 @@ impl Issuer {
         };
 
         let (tails_location, tails_hash) = tails_writer.write(&mut rev_tails_generator)?;
+        tails_writer.register(
+            &tails_hash,
+            &RevocationRegistryId::from(&rev_reg_id),
+            &cred_def.id,
+            origin_did,
+        )?;
 
         let revoc_reg_def_value = RevocationRegistryDefinitionValue {
             max_cred_num,
@@ -238,7 +246,7 @@ impl Issuer {
 
         // now update registry to reflect issuance-by-default
         let revoc_reg = if issuance_type == IssuanceType::ISSUANCE_BY_DEFAULT {
-            let tails_reader = TailsFileReader::new(&tails_location);
+            let tails_reader = tails_writer.open(&tails_location, &tails_hash)?;
             let issued = HashSet::from_iter((1..=max_cred_num).into_iter());
             let (reg, _delta) = Self::update_revocation_registry(
                 &revoc_reg_def,
@@ -287,6 +295,62 @@ impl Issuer {
         ))
     }
 
+    pub fn create_revocation_status_list(
+        rev_reg_def: &RevocationRegistryDefinition,
+        rev_reg: &RevocationRegistry,
+        issuance_type: IssuanceType,
+        timestamp: u64,
+    ) -> IndyResult<RevocationStatusList> {
+        let rev_reg_def = match rev_reg_def {
+            RevocationRegistryDefinition::RevocationRegistryDefinitionV1(v1) => v1,
+        };
+        let registry = match rev_reg {
+            RevocationRegistry::RevocationRegistryV1(v1) => v1.value.clone(),
+        };
+
+        Ok(RevocationStatusList::new(
+            &rev_reg_def.id,
+            registry,
+            rev_reg_def.value.max_cred_num,
+            issuance_type,
+            timestamp,
+        ))
+    }
+
+    pub fn update_revocation_status_list(
+        rev_reg_def: &RevocationRegistryDefinition,
+        prev_list: &RevocationStatusList,
+        issued: HashSet<u32>,
+        revoked: HashSet<u32>,
+        tails_reader: &TailsReader,
+        timestamp: u64,
+    ) -> IndyResult<RevocationStatusList> {
+        trace!(
+            "update_revocation_status_list >>> prev_list: {:?}, issued: {:?}, revoked: {:?}, timestamp: {:?}",
+            prev_list, issued, revoked, timestamp
+        );
+
+        let rev_reg_def = match rev_reg_def {
+            RevocationRegistryDefinition::RevocationRegistryDefinitionV1(v1) => v1,
+        };
+        if prev_list.max_cred_num() != rev_reg_def.value.max_cred_num {
+            return Err(err_msg(
+                IndyErrorKind::InvalidState,
+                "RevocationStatusList length does not match the registry's max_cred_num",
+            ));
+        }
+
+        let mut status_list = prev_list.clone();
+        status_list.update(issued, revoked, tails_reader, timestamp)?;
+
+        trace!(
+            "update_revocation_status_list <<< status_list: {:?}",
+            status_list
+        );
+
+        Ok(status_list)
+    }
+
     pub fn new_credential_offer(
         schema_id: &SchemaId,
         cred_def: &CredentialDefinition,
@@ -353,9 +417,7 @@ impl Issuer {
                         (&v1.value, v1.id.clone())
                     }
                 };
-                let mut rev_reg = match revocation.registry {
-                    RevocationRegistry::RevocationRegistryV1(v1) => v1.value.clone(),
-                };
+                let mut rev_reg = revocation.status_list.registry.clone();
                 let (credential_signature, signature_correctness_proof, delta) =
                     CryptoIssuer::sign_credential_with_revoc(
                         &cred_request.prover_did.0,
@@ -379,10 +441,15 @@ impl Issuer {
                     _ => Some(reg_reg_id.clone()),
                 };
                 let witness = {
-                    let used = HashSet::new(); // FIXME HashSet::from_iter((0..revocation.registry_idx).into_iter());
+                    let (mut issued, revoked) = revocation.status_list.issued_and_revoked();
                     let (by_default, issued, revoked) = match rev_reg_def.issuance_type {
-                        IssuanceType::ISSUANCE_ON_DEMAND => (false, used, HashSet::new()),
-                        IssuanceType::ISSUANCE_BY_DEFAULT => (true, HashSet::new(), used),
+                        // The credential being issued now is not yet reflected in the
+                        // tracked status list, so fold its index into `issued` here.
+                        IssuanceType::ISSUANCE_ON_DEMAND => {
+                            issued.insert(revocation.registry_idx);
+                            (false, issued, HashSet::new())
+                        }
+                        IssuanceType::ISSUANCE_BY_DEFAULT => (true, HashSet::new(), revoked),
                     };
 
                     let rev_reg_delta = CryptoRevocationRegistryDelta::from_parts(
@@ -505,11 +572,43 @@ impl Issuer {
 
         Ok(delta)
     }
+
+    /// Squash an earlier delta `a` into a later delta `b`, producing the
+    /// single delta spanning both transitions. Lets a caller that has
+    /// collected a run of published deltas fold them down with a left-fold
+    /// before a single `create_or_update_revocation_state` call, instead of
+    /// replaying them one at a time.
+    ///
+    /// `a` and `b` must be contiguous — `a`'s resulting accumulator must be
+    /// `b`'s starting accumulator — or the merged delta would assert a
+    /// revocation state the registry never actually passed through. This
+    /// crate has no public accessor onto `ursa::cl::RevocationRegistryDelta`
+    /// to compare those accumulators ourselves (the same opaque-type
+    /// constraint `Issuer::update_revocation_registry` works around by
+    /// threading `revoked` through explicitly), so the check is left to
+    /// `RevocationRegistryDelta::merge`'s own CL accumulator merge, which
+    /// rejects a non-contiguous pair as part of computing the combined
+    /// accumulator rather than as a separate guard; this wraps that failure
+    /// with a message naming the contiguity requirement, so the caller sees
+    /// why.
+    pub fn merge_revocation_registry_deltas(
+        a: &RevocationRegistryDelta,
+        b: &RevocationRegistryDelta,
+    ) -> IndyResult<RevocationRegistryDelta> {
+        let mut merged = a.clone();
+        merged.merge(b).map_err(|err| {
+            err.extend(
+                "Cannot merge revocation registry deltas: `a` is not contiguous with `b` \
+                 (`a`'s resulting accumulator must match `b`'s starting accumulator)",
+            )
+        })?;
+        Ok(merged)
+    }
 }
 
 pub struct CredentialRevocationConfig<'a> {
     pub reg_def: &'a RevocationRegistryDefinition,
-    pub registry: &'a RevocationRegistry,
+    pub status_list: &'a RevocationStatusList,
     pub registry_key: &'a RevocationKeyPrivate,
     pub registry_idx: u32,
     pub tails_reader: TailsReader,
@@ -519,9 +618,9 @@ impl<'a> std::fmt::Debug for CredentialRevocationConfig<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "CredentialRevocationConfig {{ reg_def: {:?}, registry: {:?}, key: {:?}, idx: {}, reader: {:?} }}",
+            "CredentialRevocationConfig {{ reg_def: {:?}, status_list: {:?}, key: {:?}, idx: {}, reader: {:?} }}",
             self.reg_def,
-            self.registry,
+            self.status_list,
             secret!(self.registry_key),
             secret!(self.registry_idx),
             self.tails_reader,
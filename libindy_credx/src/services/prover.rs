@@ -0,0 +1,620 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::common::did::DidValue;
+use crate::common::error::prelude::*;
+use crate::domain::credential::Credential;
+use crate::domain::credential_definition::CredentialDefinition;
+use crate::domain::credential_offer::CredentialOffer;
+use crate::domain::credential_request::{CredentialRequest, CredentialRequestMetadata};
+use crate::domain::proof::{
+    AttributeValue, Identifier, Proof, RequestedProof, RevealedAttributeGroupInfo,
+    RevealedAttributeInfo, SubProofReferent,
+};
+use crate::domain::proof_request::{matches_restriction, CredentialsForProofRequest, ProofRequest};
+use crate::domain::requested_credential::RequestedCredentials;
+use crate::domain::revocation_registry_definition::RevocationRegistryDefinition;
+use crate::domain::revocation_registry_delta::RevocationRegistryDelta;
+use crate::domain::revocation_state::RevocationState;
+use crate::domain::revocation_status_list::RevocationStatusList;
+use crate::domain::schema::Schema;
+use crate::identifiers::cred_def::CredentialDefinitionId;
+use crate::identifiers::schema::SchemaId;
+use crate::services::helpers::*;
+use crate::utils::validation::Validatable;
+
+use super::tails::TailsReader;
+use super::{new_nonce, CryptoProver, MasterSecret, Witness};
+
+pub struct Prover {}
+
+impl Prover {
+    pub fn new_master_secret() -> IndyResult<MasterSecret> {
+        trace!("new_master_secret >>>");
+
+        let master_secret = CryptoProver::new_master_secret()?;
+
+        trace!("new_master_secret <<< res: {:?}", secret!(&master_secret));
+        Ok(master_secret)
+    }
+
+    pub fn new_credential_request(
+        prover_did: &DidValue,
+        cred_def: &CredentialDefinition,
+        master_secret: &MasterSecret,
+        master_secret_id: &str,
+        cred_offer: &CredentialOffer,
+    ) -> IndyResult<(CredentialRequest, CredentialRequestMetadata)> {
+        trace!(
+            "new_credential_request >>> prover_did: {:?}, cred_def: {:?}, master_secret_id: {:?}",
+            prover_did,
+            cred_def,
+            master_secret_id
+        );
+
+        let cred_def = match cred_def {
+            CredentialDefinition::CredentialDefinitionV1(cd) => cd,
+        };
+        let cred_pub_key = cred_def.get_public_key()?;
+
+        // Only the hidden `master_secret` value is known at request time; the
+        // rest of the credential's attributes are filled in by the issuer.
+        let credential_values = build_credential_values(&HashMap::new(), Some(master_secret))?;
+        let credential_nonce = new_nonce()?;
+
+        let (blinded_ms, master_secret_blinding_data, blinded_ms_correctness_proof) =
+            CryptoProver::blind_credential_secrets(
+                &cred_pub_key,
+                &cred_offer.key_correctness_proof,
+                &credential_values,
+                &cred_offer.nonce,
+            )?;
+
+        let credential_request = CredentialRequest {
+            prover_did: prover_did.clone(),
+            cred_def_id: cred_def.id.clone(),
+            blinded_ms,
+            blinded_ms_correctness_proof,
+            nonce: credential_nonce.clone(),
+        };
+
+        let credential_request_metadata = CredentialRequestMetadata {
+            master_secret_blinding_data,
+            nonce: credential_nonce,
+            master_secret_name: master_secret_id.to_string(),
+        };
+
+        trace!(
+            "new_credential_request <<< credential_request: {:?}, credential_request_metadata: {:?}",
+            credential_request,
+            secret!(&credential_request_metadata)
+        );
+
+        Ok((credential_request, credential_request_metadata))
+    }
+
+    pub fn process_credential(
+        credential: &mut Credential,
+        cred_request_metadata: &CredentialRequestMetadata,
+        master_secret: &MasterSecret,
+        cred_def: &CredentialDefinition,
+        rev_reg_def: Option<&RevocationRegistryDefinition>,
+    ) -> IndyResult<()> {
+        trace!(
+            "process_credential >>> credential: {:?}, cred_def: {:?}",
+            secret!(&credential),
+            cred_def
+        );
+
+        let cred_def = match cred_def {
+            CredentialDefinition::CredentialDefinitionV1(cd) => cd,
+        };
+        let cred_pub_key = cred_def.get_public_key()?;
+        let credential_values = build_credential_values(&credential.values.0, Some(master_secret))?;
+        let rev_key_pub = rev_reg_def.map(|rev_reg_def| {
+            let RevocationRegistryDefinition::RevocationRegistryDefinitionV1(v1) = rev_reg_def;
+            &v1.value.public_keys.accum_key
+        });
+
+        CryptoProver::process_credential_signature(
+            &mut credential.signature,
+            &credential_values,
+            &credential.signature_correctness_proof,
+            &cred_request_metadata.master_secret_blinding_data,
+            &cred_pub_key,
+            &cred_request_metadata.nonce,
+            rev_key_pub,
+            credential.rev_reg.as_ref(),
+            credential.witness.as_ref(),
+        )?;
+
+        trace!("process_credential <<<");
+        Ok(())
+    }
+
+    /// Finds every stored credential that could satisfy each requested
+    /// attribute and predicate referent in `proof_req`, mirroring LibIndy's
+    /// `prover_search_credentials_for_proof_req`. This only narrows down
+    /// candidates — it does not pick which credential a caller should
+    /// present, so the result still has to be turned into a
+    /// `RequestedCredentials` before calling [`Prover::create_proof`].
+    ///
+    /// Attribute referents honor `name`/`names` exactly as
+    /// `build_sub_proof_request` does: a credential matches only if it
+    /// carries every named attribute, compared via `attr_common_view`.
+    /// Predicate referents additionally require the credential's own raw
+    /// value for that attribute to satisfy the `p_type`/`p_value` bound. A
+    /// referent's `restrictions` query, if present, is evaluated against the
+    /// credential's tag set (see `build_credential_tags`) via
+    /// `matches_restriction`.
+    pub fn search_credentials_for_proof_request(
+        proof_req: &ProofRequest,
+        credentials: &HashMap<String, Credential>,
+    ) -> IndyResult<CredentialsForProofRequest> {
+        trace!(
+            "search_credentials_for_proof_request >>> proof_req: {:?}",
+            proof_req
+        );
+
+        let proof_req = proof_req.value();
+        let credential_tags: HashMap<String, HashMap<String, String>> = credentials
+            .iter()
+            .map(|(cred_id, credential)| (cred_id.clone(), build_credential_tags(credential)))
+            .collect();
+
+        let mut requested_attributes = HashMap::new();
+        for (referent, attr_info) in proof_req.requested_attributes.iter() {
+            let names = if let Some(name) = &attr_info.name {
+                vec![name.clone()]
+            } else if let Some(names) = &attr_info.names {
+                names.to_owned()
+            } else {
+                return Err(input_err(
+                    r#"Attr for credential restriction should contain "name" or "names" param."#,
+                ));
+            };
+            let names: HashSet<String> = names.iter().map(|name| attr_common_view(name)).collect();
+
+            let matching = credentials
+                .iter()
+                .filter(|(cred_id, credential)| {
+                    let cred_attrs: HashSet<String> = credential
+                        .values
+                        .0
+                        .keys()
+                        .map(|name| attr_common_view(name))
+                        .collect();
+                    names.iter().all(|name| cred_attrs.contains(name))
+                        && attr_info.restrictions.as_ref().map_or(true, |restrictions| {
+                            matches_restriction(restrictions, &credential_tags[cred_id.as_str()])
+                        })
+                })
+                .map(|(cred_id, _)| cred_id.clone())
+                .collect();
+            requested_attributes.insert(referent.clone(), matching);
+        }
+
+        let mut requested_predicates = HashMap::new();
+        for (referent, predicate) in proof_req.requested_predicates.iter() {
+            let name = attr_common_view(&predicate.name);
+
+            let matching = credentials
+                .iter()
+                .filter(|(cred_id, credential)| {
+                    credential
+                        .values
+                        .0
+                        .iter()
+                        .find(|(attr, _)| attr_common_view(attr) == name)
+                        .map_or(false, |(_, values)| {
+                            satisfies_predicate(&values.raw, predicate)
+                        })
+                        && predicate.restrictions.as_ref().map_or(true, |restrictions| {
+                            matches_restriction(restrictions, &credential_tags[cred_id.as_str()])
+                        })
+                })
+                .map(|(cred_id, _)| cred_id.clone())
+                .collect();
+            requested_predicates.insert(referent.clone(), matching);
+        }
+
+        let result = CredentialsForProofRequest {
+            requested_attributes,
+            requested_predicates,
+        };
+
+        trace!(
+            "search_credentials_for_proof_request <<< res: {:?}",
+            result
+        );
+        Ok(result)
+    }
+
+    pub fn create_proof(
+        proof_req: &ProofRequest,
+        credentials: &HashMap<String, Credential>,
+        requested_credentials: &RequestedCredentials,
+        master_secret: &MasterSecret,
+        schemas: &HashMap<SchemaId, &Schema>,
+        cred_defs: &HashMap<CredentialDefinitionId, &CredentialDefinition>,
+        rev_states: &HashMap<String, Vec<&RevocationState>>,
+    ) -> IndyResult<Proof> {
+        trace!("create_proof >>> proof_req: {:?}", proof_req);
+
+        let proof_req = proof_req.value();
+
+        // Group every requested attribute/predicate referent by the
+        // (cred_id, timestamp) pair it is being proved against, so each
+        // credential contributes a single sub proof per timestamp.
+        let mut keys: Vec<(String, Option<u64>)> = Vec::new();
+        let mut attr_referents: HashMap<(String, Option<u64>), Vec<String>> = HashMap::new();
+        let mut pred_referents: HashMap<(String, Option<u64>), Vec<String>> = HashMap::new();
+
+        for (referent, requested) in requested_credentials.requested_attributes.iter() {
+            let key = (requested.cred_id.clone(), requested.timestamp);
+            if !attr_referents.contains_key(&key) && !pred_referents.contains_key(&key) {
+                keys.push(key.clone());
+            }
+            attr_referents
+                .entry(key)
+                .or_insert_with(Vec::new)
+                .push(referent.clone());
+        }
+        for (referent, requested) in requested_credentials.requested_predicates.iter() {
+            let key = (requested.cred_id.clone(), requested.timestamp);
+            if !attr_referents.contains_key(&key) && !pred_referents.contains_key(&key) {
+                keys.push(key.clone());
+            }
+            pred_referents
+                .entry(key)
+                .or_insert_with(Vec::new)
+                .push(referent.clone());
+        }
+
+        let mut proof_builder = CryptoProver::new_proof_builder()?;
+        proof_builder.add_common_attribute("master_secret")?;
+
+        let mut requested_proof = RequestedProof::default();
+        requested_proof.self_attested_attrs = requested_credentials.self_attested_attributes.clone();
+
+        let mut identifiers = Vec::new();
+
+        for key @ (cred_id, timestamp) in keys {
+            let credential = credentials
+                .get(&cred_id)
+                .ok_or_else(|| input_err(format!("Credential not provided for id \"{}\"", cred_id)))?;
+            let schema = schemas
+                .get(&credential.schema_id)
+                .ok_or_else(|| input_err(format!("Schema not provided for id \"{}\"", credential.schema_id)))?;
+            let schema = match schema {
+                Schema::SchemaV1(s) => s,
+            };
+            let cred_def = cred_defs.get(&credential.cred_def_id).ok_or_else(|| {
+                input_err(format!(
+                    "Credential definition not provided for id \"{}\"",
+                    credential.cred_def_id
+                ))
+            })?;
+            let cred_def_v1 = match cred_def {
+                CredentialDefinition::CredentialDefinitionV1(cd) => cd,
+            };
+
+            let attrs_for_cred = attr_referents
+                .get(&key)
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|referent| proof_req.requested_attributes[&referent].clone())
+                .collect::<Vec<_>>();
+            let preds_for_cred = pred_referents
+                .get(&key)
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|referent| proof_req.requested_predicates[&referent].clone())
+                .collect::<Vec<_>>();
+
+            let sub_proof_request = build_sub_proof_request(&attrs_for_cred, &preds_for_cred)?;
+            let credential_schema = build_credential_schema(&schema.attr_names.0)?;
+            let non_credential_schema = build_non_credential_schema()?;
+            let credential_values = build_credential_values(&credential.values.0, Some(master_secret))?;
+            let cred_pub_key = cred_def_v1.get_public_key()?;
+
+            let (rev_reg, witness) = match timestamp {
+                Some(timestamp) => {
+                    let state = rev_states
+                        .get(&cred_id)
+                        .and_then(|states| states.iter().find(|s| s.timestamp == timestamp))
+                        .ok_or_else(|| {
+                            input_err(format!(
+                                "Revocation state not provided for credential \"{}\" at timestamp {}",
+                                cred_id, timestamp
+                            ))
+                        })?;
+                    (Some(&state.rev_reg), Some(&state.witness))
+                }
+                None => (None, None),
+            };
+
+            let sub_proof_index = identifiers.len() as u32;
+
+            proof_builder.add_sub_proof_request(
+                &sub_proof_request,
+                &credential_schema,
+                &non_credential_schema,
+                &credential.signature,
+                &credential_values,
+                &cred_pub_key,
+                rev_reg,
+                witness,
+            )?;
+
+            identifiers.push(Identifier {
+                schema_id: credential.schema_id.clone(),
+                cred_def_id: credential.cred_def_id.clone(),
+                rev_reg_id: credential.rev_reg_id.clone(),
+                timestamp,
+            });
+
+            if let Some(referents) = attr_referents.get(&key) {
+                for referent in referents {
+                    let requested = &requested_credentials.requested_attributes[referent];
+                    let attr_info = &proof_req.requested_attributes[referent];
+                    if !requested.revealed {
+                        requested_proof
+                            .unrevealed_attrs
+                            .insert(referent.clone(), SubProofReferent { sub_proof_index });
+                        continue;
+                    }
+                    if let Some(name) = &attr_info.name {
+                        let values = credential.values.0.get(name).ok_or_else(|| {
+                            input_err(format!(
+                                "Credential \"{}\" is missing a value for attribute \"{}\"",
+                                cred_id, name
+                            ))
+                        })?;
+                        requested_proof.revealed_attrs.insert(
+                            referent.clone(),
+                            RevealedAttributeInfo {
+                                sub_proof_index,
+                                raw: values.raw.clone(),
+                                encoded: values.encoded.clone(),
+                            },
+                        );
+                    } else if let Some(names) = &attr_info.names {
+                        let mut group = HashMap::new();
+                        for name in names {
+                            let values = credential.values.0.get(name).ok_or_else(|| {
+                                input_err(format!(
+                                    "Credential \"{}\" is missing a value for attribute \"{}\"",
+                                    cred_id, name
+                                ))
+                            })?;
+                            group.insert(
+                                name.clone(),
+                                AttributeValue {
+                                    raw: values.raw.clone(),
+                                    encoded: values.encoded.clone(),
+                                },
+                            );
+                        }
+                        requested_proof.revealed_attr_groups.insert(
+                            referent.clone(),
+                            RevealedAttributeGroupInfo {
+                                sub_proof_index,
+                                values: group,
+                            },
+                        );
+                    }
+                }
+            }
+
+            if let Some(referents) = pred_referents.get(&key) {
+                for referent in referents {
+                    requested_proof
+                        .predicates
+                        .insert(referent.clone(), SubProofReferent { sub_proof_index });
+                }
+            }
+        }
+
+        let proof = proof_builder.finalize(&proof_req.nonce)?;
+
+        let proof = Proof {
+            proof,
+            requested_proof,
+            identifiers,
+        };
+
+        trace!("create_proof <<< proof: {:?}", secret!(&proof));
+        Ok(proof)
+    }
+
+    /// Build (or incrementally extend) a non-revocation witness from a
+    /// single published `RevocationRegistryDelta`.
+    pub fn create_or_update_revocation_state(
+        tails_reader: TailsReader,
+        revoc_reg_def: &RevocationRegistryDefinition,
+        rev_reg_delta: &RevocationRegistryDelta,
+        rev_reg_idx: u32,
+        timestamp: u64,
+        rev_state: Option<RevocationState>,
+    ) -> IndyResult<RevocationState> {
+        trace!(
+            "create_or_update_revocation_state >>> rev_reg_idx: {:?}, timestamp: {:?}",
+            rev_reg_idx,
+            timestamp
+        );
+
+        let RevocationRegistryDefinition::RevocationRegistryDefinitionV1(revoc_reg_def) =
+            revoc_reg_def;
+        let RevocationRegistryDelta::RevocationRegistryDeltaV1(rev_reg_delta) = rev_reg_delta;
+
+        let witness = match rev_state {
+            Some(source_rev_state) => {
+                let mut witness = source_rev_state.witness;
+                witness.update(
+                    rev_reg_idx,
+                    revoc_reg_def.value.max_cred_num,
+                    &rev_reg_delta.value,
+                    &tails_reader,
+                )?;
+                witness
+            }
+            None => Witness::new(
+                rev_reg_idx,
+                revoc_reg_def.value.max_cred_num,
+                revoc_reg_def.value.issuance_type.to_bool(),
+                &rev_reg_delta.value,
+                &tails_reader,
+            )?,
+        };
+
+        let rev_state = RevocationState {
+            witness,
+            rev_reg: super::CryptoRevocationRegistry::from(&rev_reg_delta.value),
+            timestamp,
+        };
+
+        trace!("create_or_update_revocation_state <<< rev_state: {:?}", rev_state);
+        Ok(rev_state)
+    }
+
+    /// Build (or incrementally extend) a non-revocation witness by diffing
+    /// two complete `RevocationStatusList` snapshots instead of consuming a
+    /// `RevocationRegistryDelta`. This sidesteps the ordering and resync
+    /// problems that come from having to replay a chain of deltas: the
+    /// caller only ever needs the status list it last used and the one it
+    /// wants to prove against now.
+    pub fn create_or_update_revocation_state_from_status_lists(
+        tails_reader: TailsReader,
+        revoc_reg_def: &RevocationRegistryDefinition,
+        prev_status_list: Option<&RevocationStatusList>,
+        status_list: &RevocationStatusList,
+        rev_reg_idx: u32,
+        rev_state: Option<RevocationState>,
+    ) -> IndyResult<RevocationState> {
+        trace!(
+            "create_or_update_revocation_state_from_status_lists >>> rev_reg_idx: {:?}, timestamp: {:?}",
+            rev_reg_idx,
+            status_list.timestamp
+        );
+
+        let RevocationRegistryDefinition::RevocationRegistryDefinitionV1(revoc_reg_def) =
+            revoc_reg_def;
+        if status_list.max_cred_num() != revoc_reg_def.value.max_cred_num {
+            return Err(err_msg(
+                IndyErrorKind::InvalidState,
+                "RevocationStatusList length does not match the registry's max_cred_num",
+            ));
+        }
+
+        let (issued, revoked) = status_list.issued_and_revoked();
+        let (prev_issued, prev_revoked) = prev_status_list
+            .map(RevocationStatusList::issued_and_revoked)
+            .unwrap_or_default();
+        let newly_issued = issued
+            .difference(&prev_issued)
+            .copied()
+            .collect::<HashSet<_>>();
+        let newly_revoked = revoked
+            .difference(&prev_revoked)
+            .copied()
+            .collect::<HashSet<_>>();
+
+        let rev_reg_delta = super::CryptoRevocationRegistryDelta::from_parts(
+            None,
+            &status_list.registry,
+            &newly_issued,
+            &newly_revoked,
+        );
+
+        let witness = match rev_state {
+            Some(source_rev_state) => {
+                let mut witness = source_rev_state.witness;
+                witness.update(
+                    rev_reg_idx,
+                    revoc_reg_def.value.max_cred_num,
+                    &rev_reg_delta,
+                    &tails_reader,
+                )?;
+                witness
+            }
+            None => Witness::new(
+                rev_reg_idx,
+                revoc_reg_def.value.max_cred_num,
+                revoc_reg_def.value.issuance_type.to_bool(),
+                &rev_reg_delta,
+                &tails_reader,
+            )?,
+        };
+
+        let rev_state = RevocationState {
+            witness,
+            rev_reg: status_list.registry.clone(),
+            timestamp: status_list.timestamp,
+        };
+
+        trace!(
+            "create_or_update_revocation_state_from_status_lists <<< rev_state: {:?}",
+            rev_state
+        );
+        Ok(rev_state)
+    }
+
+    /// Advances `rev_state`'s non-revocation witness to match a freshly
+    /// published `RevocationRegistryDelta`, so a long-lived holder can keep a
+    /// credential's proof current cheaply instead of rebuilding the witness
+    /// from the full tails file every interval. `revoked` must be the same
+    /// index set the issuer published alongside `rev_reg_delta` (see
+    /// `Issuer::update_revocation_registry`): `ursa`'s
+    /// `RevocationRegistryDelta` does not expose which indices it retired,
+    /// so the caller has to carry that set forward out of band. If the
+    /// credential's own `rev_reg_idx` is among them the delta can never be
+    /// used to prove non-revocation again, so this returns a
+    /// `CredentialRevoked` error rather than a witness that would look valid
+    /// but is stale.
+    pub fn update_revocation_state(
+        tails_reader: TailsReader,
+        revoc_reg_def: &RevocationRegistryDefinition,
+        rev_reg_delta: &RevocationRegistryDelta,
+        revoked: &HashSet<u32>,
+        rev_reg_idx: u32,
+        timestamp: u64,
+        rev_state: RevocationState,
+    ) -> IndyResult<RevocationState> {
+        trace!(
+            "update_revocation_state >>> rev_reg_idx: {:?}, timestamp: {:?}",
+            rev_reg_idx,
+            timestamp
+        );
+
+        if revoked.contains(&rev_reg_idx) {
+            return Err(err_msg(
+                IndyErrorKind::CredentialRevoked,
+                "Cannot update non-revocation witness: credential has been revoked",
+            ));
+        }
+
+        let RevocationRegistryDefinition::RevocationRegistryDefinitionV1(revoc_reg_def) =
+            revoc_reg_def;
+        let RevocationRegistryDelta::RevocationRegistryDeltaV1(rev_reg_delta) = rev_reg_delta;
+
+        let mut witness = rev_state.witness;
+        witness.update(
+            rev_reg_idx,
+            revoc_reg_def.value.max_cred_num,
+            &rev_reg_delta.value,
+            &tails_reader,
+        )?;
+
+        let rev_state = RevocationState {
+            witness,
+            rev_reg: super::CryptoRevocationRegistry::from(&rev_reg_delta.value),
+            timestamp,
+        };
+        rev_state.validate()?;
+
+        trace!("update_revocation_state <<< rev_state: {:?}", rev_state);
+        Ok(rev_state)
+    }
+}
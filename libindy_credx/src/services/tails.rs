@@ -0,0 +1,457 @@
+use std::cell::RefCell;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+use ursa::errors::{UrsaCryptoError, UrsaCryptoErrorKind};
+use ursa::hash::sha2::{Digest, Sha256};
+
+use crate::common::did::DidValue;
+use crate::common::error::prelude::*;
+use crate::identifiers::cred_def::CredentialDefinitionId;
+use crate::identifiers::rev_reg::RevocationRegistryId;
+
+use super::{RevocationTailsAccessor, RevocationTailsGenerator, Tail};
+
+const TAILS_BLOB_TAG_SZ: usize = 2;
+const TAILS_INDEX_FILE_NAME: &str = "tails_index.json";
+
+/// Any source a `TailsReader` can pull a tails blob's raw bytes from.
+pub trait TailsReaderImpl: std::fmt::Debug + Send {
+    fn read(&mut self, size: usize, offset: usize) -> IndyResult<Vec<u8>>;
+}
+
+/// Wraps a `TailsReaderImpl` and implements ursa's `RevocationTailsAccessor`,
+/// which is the concrete shape expected by the credential issuance and
+/// witness-construction APIs.
+#[derive(Debug)]
+pub struct TailsReader(RefCell<Box<dyn TailsReaderImpl>>);
+
+impl TailsReader {
+    pub fn new(impl_: impl TailsReaderImpl + 'static) -> Self {
+        Self(RefCell::new(Box::new(impl_)))
+    }
+}
+
+impl RevocationTailsAccessor for TailsReader {
+    fn access_tail(
+        &self,
+        tail_id: u32,
+        accessor: &mut dyn FnMut(&Tail),
+    ) -> Result<(), UrsaCryptoError> {
+        let tail_size = std::mem::size_of::<Tail>();
+        let offset = TAILS_BLOB_TAG_SZ + (tail_id as usize) * tail_size;
+        let bytes = self
+            .0
+            .borrow_mut()
+            .read(tail_size, offset)
+            .map_err(|err| UrsaCryptoError::from_msg(UrsaCryptoErrorKind::InvalidState, err.to_string()))?;
+        let tail = Tail::from_bytes(&bytes)
+            .map_err(|err| UrsaCryptoError::from_msg(UrsaCryptoErrorKind::InvalidState, err.to_string()))?;
+        accessor(&tail);
+        Ok(())
+    }
+}
+
+/// A pluggable tails blob storage backend, modeled on the reader/writer
+/// config handles the legacy libindy anoncreds API routed tails through.
+/// `TailsWriter` is a thin adapter over this trait, so swapping storage
+/// (local directory, in-memory, a future network blob store) only requires
+/// a new `BlobStorage` impl rather than touching the issuer workflow.
+pub trait BlobStorage: Send {
+    /// Store the bytes read from `source`, returning the location to record
+    /// in the registry definition along with the blob's content hash.
+    fn put(&mut self, source: &mut dyn Read) -> IndyResult<(String, String)>;
+
+    /// Open a previously-stored blob for the random-access reads the
+    /// witness-construction APIs perform.
+    fn open(&self, location: &str, hash: &str) -> IndyResult<TailsReader>;
+}
+
+fn write_tails(generator: &mut RevocationTailsGenerator, out: &mut impl Write) -> IndyResult<()> {
+    let version = &[0u8, 2u8][..];
+    out.write_all(version)?;
+    while let Some(tail) = generator.try_next()? {
+        out.write_all(&tail.to_bytes()?)?;
+    }
+    Ok(())
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::default();
+    hasher.input(bytes);
+    hex::encode(hasher.result())
+}
+
+/// Writes a freshly-generated tails blob to a `BlobStorage` backend and
+/// optionally maintains a `TailsStore` index alongside it, so a single
+/// writer instance can service both the tails-generator output and any
+/// later re-read of that same blob (e.g. for `ISSUANCE_BY_DEFAULT` setup)
+/// without re-opening storage by path.
+pub struct TailsWriter<B: BlobStorage> {
+    backend: B,
+    index: Option<TailsStore>,
+}
+
+impl<B: BlobStorage> TailsWriter<B> {
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            index: None,
+        }
+    }
+
+    pub fn with_index(backend: B, index: TailsStore) -> Self {
+        Self {
+            backend,
+            index: Some(index),
+        }
+    }
+
+    pub fn write(&mut self, generator: &mut RevocationTailsGenerator) -> IndyResult<(String, String)> {
+        let mut buf = Vec::new();
+        write_tails(generator, &mut buf)?;
+        self.backend.put(&mut &buf[..])
+    }
+
+    pub fn open(&self, location: &str, hash: &str) -> IndyResult<TailsReader> {
+        self.backend.open(location, hash)
+    }
+
+    /// Record which registry, credential definition and issuer a previously
+    /// written tails blob (identified by `hash`) belongs to, so it can later
+    /// be looked up or garbage-collected by any of those identifiers. A
+    /// writer with no `index` attached (e.g. over `MemoryBlobStorage`) is a
+    /// no-op.
+    pub fn register(
+        &mut self,
+        hash: &str,
+        rev_reg_id: &RevocationRegistryId,
+        cred_def_id: &CredentialDefinitionId,
+        issuer_did: &DidValue,
+    ) -> IndyResult<()> {
+        match &self.index {
+            Some(index) => index.register(hash, rev_reg_id, cred_def_id, issuer_did),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TailsFileReaderImpl {
+    path: PathBuf,
+}
+
+impl TailsReaderImpl for TailsFileReaderImpl {
+    fn read(&mut self, size: usize, offset: usize) -> IndyResult<Vec<u8>> {
+        let mut file = fs::File::open(&self.path)?;
+        file.seek(SeekFrom::Start(offset as u64))?;
+        let mut buf = vec![0u8; size];
+        file.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+/// Reads a tails blob that already sits on the local filesystem, independent
+/// of any `BlobStorage` instance.
+pub struct TailsFileReader {}
+
+impl TailsFileReader {
+    pub fn new(tails_location: &str) -> TailsReader {
+        TailsReader::new(TailsFileReaderImpl {
+            path: PathBuf::from(tails_location),
+        })
+    }
+
+    /// Opens a tails file after verifying its SHA-256 matches `tails_hash`,
+    /// failing fast with `InvalidState` rather than silently handing a
+    /// corrupted or substituted blob to the witness-construction APIs.
+    pub fn open(tails_location: &str, tails_hash: &str) -> IndyResult<TailsReader> {
+        let actual_hash = hash_bytes(&fs::read(tails_location)?);
+        if actual_hash != tails_hash {
+            return Err(err_msg(
+                IndyErrorKind::InvalidState,
+                format!(
+                    "Tails file hash mismatch: expected {}, got {}",
+                    tails_hash, actual_hash
+                ),
+            ));
+        }
+        Ok(Self::new(tails_location))
+    }
+}
+
+/// Stores tails blobs in a directory, naming each output file after its
+/// content hash.
+pub struct FileBlobStorage {
+    dir_path: PathBuf,
+}
+
+impl FileBlobStorage {
+    pub fn new(dir_path: Option<String>) -> Self {
+        Self {
+            dir_path: dir_path.map(PathBuf::from).unwrap_or_else(std::env::temp_dir),
+        }
+    }
+}
+
+impl BlobStorage for FileBlobStorage {
+    fn put(&mut self, source: &mut dyn Read) -> IndyResult<(String, String)> {
+        fs::create_dir_all(&self.dir_path)?;
+        let temp_path = self.dir_path.join(format!(".{}", uuid::Uuid::new_v4()));
+        let hash = {
+            let mut file = fs::File::create(&temp_path)?;
+            let mut hasher = Sha256::default();
+            let mut buf = Vec::new();
+            source.read_to_end(&mut buf)?;
+            hasher.input(&buf);
+            file.write_all(&buf)?;
+            hex::encode(hasher.result())
+        };
+        let final_path = self.dir_path.join(&hash);
+        fs::rename(&temp_path, &final_path)?;
+        Ok((
+            final_path
+                .to_str()
+                .ok_or_else(|| err_msg(IndyErrorKind::IOError, "Invalid tails path"))?
+                .to_owned(),
+            hash,
+        ))
+    }
+
+    fn open(&self, location: &str, hash: &str) -> IndyResult<TailsReader> {
+        TailsFileReader::open(location, hash)
+    }
+}
+
+/// A `TailsWriter` over a directory on the local filesystem, with a
+/// `TailsStore` index kept alongside the blobs.
+pub type TailsFileWriter = TailsWriter<FileBlobStorage>;
+
+impl TailsFileWriter {
+    pub fn new(dir_path: Option<String>) -> Self {
+        let backend = FileBlobStorage::new(dir_path);
+        let index = TailsStore::new(backend.dir_path.clone());
+        Self::with_index(backend, index)
+    }
+}
+
+/// A single sidecar-index entry mapping a written tails blob to the
+/// identifiers of the registry, credential definition and issuer that
+/// produced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TailsIndexEntry {
+    pub rev_reg_id: RevocationRegistryId,
+    pub cred_def_id: CredentialDefinitionId,
+    pub issuer_did: DidValue,
+    pub hash: String,
+}
+
+/// Keeps a small JSON sidecar index (`tails_index.json`) alongside a
+/// directory of tails blobs, so blobs can be looked up or garbage-collected
+/// by the identifiers of the registry/cred-def/issuer they belong to instead
+/// of only by their content hash.
+pub struct TailsStore {
+    dir_path: PathBuf,
+}
+
+impl TailsStore {
+    pub fn new(dir_path: PathBuf) -> Self {
+        Self { dir_path }
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.dir_path.join(TAILS_INDEX_FILE_NAME)
+    }
+
+    fn load_index(&self) -> IndyResult<Vec<TailsIndexEntry>> {
+        let path = self.index_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let data = fs::read_to_string(&path)?;
+        // A corrupt or foreign file squatting on the reserved index name should
+        // not take down the whole store: treat it as an empty index rather
+        // than failing every subsequent registration or lookup.
+        Ok(serde_json::from_str(&data).unwrap_or_default())
+    }
+
+    fn save_index(&self, entries: &[TailsIndexEntry]) -> IndyResult<()> {
+        fs::create_dir_all(&self.dir_path)?;
+        let data = serde_json::to_string(entries)
+            .to_indy(IndyErrorKind::InvalidState, "Error serializing tails index")?;
+        fs::write(self.index_path(), data)?;
+        Ok(())
+    }
+
+    /// Record that `hash` belongs to the given registry/cred-def/issuer.
+    pub fn register(
+        &self,
+        hash: &str,
+        rev_reg_id: &RevocationRegistryId,
+        cred_def_id: &CredentialDefinitionId,
+        issuer_did: &DidValue,
+    ) -> IndyResult<()> {
+        let mut entries = self.load_index()?;
+        entries.retain(|entry| entry.hash != hash);
+        entries.push(TailsIndexEntry {
+            rev_reg_id: rev_reg_id.clone(),
+            cred_def_id: cred_def_id.clone(),
+            issuer_did: issuer_did.clone(),
+            hash: hash.to_owned(),
+        });
+        self.save_index(&entries)
+    }
+
+    /// List all known index entries.
+    pub fn list(&self) -> IndyResult<Vec<TailsIndexEntry>> {
+        self.load_index()
+    }
+
+    fn remove_where(&self, keep: impl Fn(&TailsIndexEntry) -> bool) -> IndyResult<usize> {
+        let entries = self.load_index()?;
+        let (removed, kept): (Vec<_>, Vec<_>) = entries.into_iter().partition(|entry| !keep(entry));
+        for entry in &removed {
+            let _ = fs::remove_file(self.dir_path.join(&entry.hash));
+        }
+        self.save_index(&kept)?;
+        Ok(removed.len())
+    }
+
+    pub fn remove_by_rev_reg_id(&self, rev_reg_id: &RevocationRegistryId) -> IndyResult<usize> {
+        self.remove_where(|entry| &entry.rev_reg_id != rev_reg_id)
+    }
+
+    pub fn remove_by_cred_def_id(&self, cred_def_id: &CredentialDefinitionId) -> IndyResult<usize> {
+        self.remove_where(|entry| &entry.cred_def_id != cred_def_id)
+    }
+
+    pub fn remove_by_issuer_did(&self, issuer_did: &DidValue) -> IndyResult<usize> {
+        self.remove_where(|entry| &entry.issuer_did != issuer_did)
+    }
+}
+
+/// Stores tails blobs in memory, keyed by content hash. Useful for tests
+/// and ephemeral issuers that do not want to touch the filesystem.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryBlobStorage(std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, Vec<u8>>>>);
+
+impl MemoryBlobStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl BlobStorage for MemoryBlobStorage {
+    fn put(&mut self, source: &mut dyn Read) -> IndyResult<(String, String)> {
+        let mut buf = Vec::new();
+        source.read_to_end(&mut buf)?;
+        let hash = hash_bytes(&buf);
+        self.0
+            .lock()
+            .map_err(|_| err_msg(IndyErrorKind::InvalidState, "Tails memory storage poisoned"))?
+            .insert(hash.clone(), buf);
+        Ok((hash.clone(), hash))
+    }
+
+    fn open(&self, location: &str, _hash: &str) -> IndyResult<TailsReader> {
+        let blob = self
+            .0
+            .lock()
+            .map_err(|_| err_msg(IndyErrorKind::InvalidState, "Tails memory storage poisoned"))?
+            .get(location)
+            .cloned()
+            .ok_or_else(|| err_msg(IndyErrorKind::Input, format!("No tails blob for hash {}", location)))?;
+        Ok(TailsReader::new(TailsMemoryReaderImpl { blob }))
+    }
+}
+
+#[derive(Debug)]
+struct TailsMemoryReaderImpl {
+    blob: Vec<u8>,
+}
+
+impl TailsReaderImpl for TailsMemoryReaderImpl {
+    fn read(&mut self, size: usize, offset: usize) -> IndyResult<Vec<u8>> {
+        self.blob
+            .get(offset..offset + size)
+            .map(|s| s.to_vec())
+            .ok_or_else(|| err_msg(IndyErrorKind::IOError, "Read past end of tails blob"))
+    }
+}
+
+/// A `TailsWriter` over an in-memory `MemoryBlobStorage`, with no index
+/// attached (`register` is a no-op).
+pub type TailsMemoryStorage = TailsWriter<MemoryBlobStorage>;
+
+impl TailsMemoryStorage {
+    pub fn new() -> Self {
+        Self::new_over(MemoryBlobStorage::new())
+    }
+
+    fn new_over(backend: MemoryBlobStorage) -> Self {
+        TailsWriter::new(backend)
+    }
+}
+
+/// Downloads tails blobs published over HTTP (the `von_tails`-style pattern
+/// of serving a registry's tails file keyed by its hash), caching each one
+/// locally on first access and verifying its SHA-256 against the expected
+/// `tails_hash` before use.
+pub struct HttpTailsReader {
+    base_url: String,
+    tails_hash: String,
+    cache_dir: PathBuf,
+}
+
+impl HttpTailsReader {
+    pub fn new(base_url: &str, tails_hash: &str, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            tails_hash: tails_hash.to_owned(),
+            cache_dir: cache_dir.into(),
+        }
+    }
+
+    fn cached_path(&self) -> PathBuf {
+        self.cache_dir.join(&self.tails_hash)
+    }
+
+    fn fetch(&self) -> IndyResult<PathBuf> {
+        let cached_path = self.cached_path();
+        if cached_path.exists() {
+            return Ok(cached_path);
+        }
+
+        fs::create_dir_all(&self.cache_dir)?;
+        let url = format!("{}/{}", self.base_url, self.tails_hash);
+        let bytes = reqwest::blocking::get(&url)
+            .and_then(|resp| resp.error_for_status())
+            .and_then(|resp| resp.bytes())
+            .map_err(|err| err_msg(IndyErrorKind::IOError, format!("Error fetching tails file from {}: {}", url, err)))?;
+
+        let actual_hash = hash_bytes(&bytes);
+        if actual_hash != self.tails_hash {
+            return Err(err_msg(
+                IndyErrorKind::Input,
+                format!(
+                    "Tails file hash mismatch: expected {}, got {}",
+                    self.tails_hash, actual_hash
+                ),
+            ));
+        }
+
+        let temp_path = self.cache_dir.join(format!(".{}", uuid::Uuid::new_v4()));
+        fs::write(&temp_path, &bytes)?;
+        fs::rename(&temp_path, &cached_path)?;
+        Ok(cached_path)
+    }
+
+    pub fn into_reader(self) -> IndyResult<TailsReader> {
+        let path = self.fetch()?;
+        Ok(TailsFileReader::new(
+            path.to_str()
+                .ok_or_else(|| err_msg(IndyErrorKind::IOError, "Invalid tails cache path"))?,
+        ))
+    }
+}
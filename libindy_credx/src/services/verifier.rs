@@ -1,24 +1,53 @@
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
 use regex::Regex;
+use ursa::cl::Proof as CryptoProof;
 
 use crate::common::error::prelude::*;
+use crate::domain::credential::AttributeValues;
 use crate::domain::credential_definition::CredentialDefinition;
-use crate::domain::proof::{Identifier, Proof, RequestedProof, RevealedAttributeInfo};
+use crate::domain::proof::{
+    AttributeValue, Identifier, Proof, RequestedProof, RevealedAttributeGroupInfo,
+    RevealedAttributeInfo, SubProofReferent,
+};
 use crate::domain::proof_request::{
     AttributeInfo, NonRevocedInterval, PredicateInfo, ProofRequest, ProofRequestPayload,
 };
 use crate::domain::revocation_registry::RevocationRegistry;
 use crate::domain::revocation_registry_definition::RevocationRegistryDefinition;
+use crate::domain::revocation_status_list::RevocationStatusList;
 use crate::domain::schema::Schema;
+use crate::domain::w3c_presentation::{
+    CredentialAttributeValue, W3CCredentialPresentation, W3CPresentation,
+};
 use crate::identifiers::cred_def::CredentialDefinitionId;
-use crate::identifiers::rev_reg::RevocationRegistryId;
+use crate::identifiers::rev_reg_def::RevocationRegistryDefinitionId;
 use crate::identifiers::schema::SchemaId;
 use crate::services::helpers::*;
 use crate::utils::wql::Query;
 
 use super::{new_nonce, CredentialPublicKey, CryptoVerifier, Nonce};
 
+/// The fixed (non-attribute) tags a restriction `Query` can be checked
+/// against during verification, gathered per-referent from the proof's own
+/// `Identifier`. This mirrors the `schema_id`/`schema_name`/`schema_version`/
+/// `schema_issuer_did`/`issuer_did`/`cred_def_id`/`rev_reg_id` tag namespace
+/// that `services::helpers::build_credential_tags` also produces on the
+/// prover side for `Prover::search_credentials_for_proof_request`.
+///
+/// Verification keeps its own `_do_process_operator` walk over this `Filter`
+/// rather than delegating to `domain::proof_request::matches_restriction`
+/// (the evaluator the prover's credential search uses): a failed restriction
+/// here has to explain which tag/operator/expected-vs-actual value rejected
+/// the proof (`_do_process_operator`'s per-leaf `err.extend(...)` chain, and
+/// the separate `_do_process_operator_collect` walk backing
+/// `diagnose_requested_restrictions`), and its `$gt`/`$gte`/`$lt`/`$lte`
+/// comparisons fall back to arbitrary precision where `matches_restriction`
+/// only compares values that fit in an `i64`. Both
+/// evaluators are exercised against the same restrictions in
+/// `tests::do_process_operator_agrees_with_matches_restriction` below to
+/// guard against their behavior drifting apart on the operators they share.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Filter {
     schema_id: String,
@@ -27,12 +56,70 @@ pub struct Filter {
     schema_version: String,
     issuer_did: String,
     cred_def_id: String,
+    rev_reg_id: Option<String>,
 }
 
 lazy_static! {
     static ref INTERNAL_TAG_MATCHER: Regex = Regex::new("^attr::([^:]+)::(value|marker)$").unwrap();
 }
 
+/// Controls whether a verifier accepts self-attested values (free-text
+/// claims with no backing credential) for requested-attribute referents
+/// that have no restrictions. Defaults to allowing self-attestation
+/// everywhere, matching this crate's historical behavior; a verifier that
+/// wants to insist on credential-backed values for specific referents (or
+/// for all of them) should pass a `VerificationPolicy` that says so.
+#[derive(Debug, Clone)]
+pub struct VerificationPolicy {
+    pub allow_self_attested: bool,
+    pub self_attested_denylist: HashSet<String>,
+}
+
+impl Default for VerificationPolicy {
+    fn default() -> Self {
+        Self {
+            allow_self_attested: true,
+            self_attested_denylist: HashSet::new(),
+        }
+    }
+}
+
+impl VerificationPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn permits_self_attested(&self, referent: &str) -> bool {
+        self.allow_self_attested && !self.self_attested_denylist.contains(referent)
+    }
+}
+
+/// A single restriction comparison that failed during
+/// `Verifier::diagnose_requested_restrictions`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RestrictionFailure {
+    pub referent: String,
+    pub operator: String,
+    pub tag: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// The full set of restriction failures found while diagnosing a proof,
+/// as opposed to the single `IndyError` the fast short-circuiting
+/// `verify_proof` path stops at. Empty `failures` means every requested
+/// restriction was satisfied.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RestrictionReport {
+    pub failures: Vec<RestrictionFailure>,
+}
+
+impl RestrictionReport {
+    pub fn is_accepted(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
 pub struct Verifier {}
 
 impl Verifier {
@@ -45,11 +132,14 @@ impl Verifier {
         proof_req: &ProofRequest,
         schemas: &HashMap<SchemaId, &Schema>,
         cred_defs: &HashMap<CredentialDefinitionId, &CredentialDefinition>,
-        rev_reg_defs: &HashMap<RevocationRegistryId, &RevocationRegistryDefinition>,
-        rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, &RevocationRegistry>>,
+        rev_reg_defs: &HashMap<RevocationRegistryDefinitionId, &RevocationRegistryDefinition>,
+        rev_regs: &HashMap<RevocationRegistryDefinitionId, HashMap<u64, &RevocationRegistry>>,
+        rev_status_lists: Option<&HashMap<RevocationRegistryDefinitionId, &RevocationStatusList>>,
+        timestamp_tolerance: Option<u64>,
+        policy: Option<&VerificationPolicy>,
     ) -> IndyResult<bool> {
-        trace!("verify >>> full_proof: {:?}, proof_req: {:?}, schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?} rev_regs: {:?}",
-               full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs);
+        trace!("verify >>> full_proof: {:?}, proof_req: {:?}, schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?} rev_regs: {:?} rev_status_lists: {:?} timestamp_tolerance: {:?} policy: {:?}",
+               full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs, rev_status_lists, timestamp_tolerance, policy.map(|p| (p.allow_self_attested, &p.self_attested_denylist)));
 
         let proof_req = proof_req.value();
         let received_revealed_attrs: HashMap<String, Identifier> =
@@ -61,6 +151,8 @@ impl Verifier {
         let received_self_attested_attrs: HashSet<String> =
             Self::_received_self_attested_attrs(&full_proof);
 
+        Self::_enforce_self_attested_policy(proof_req, &received_self_attested_attrs, policy)?;
+
         Self::_compare_attr_from_proof_and_request(
             proof_req,
             &received_revealed_attrs,
@@ -86,6 +178,7 @@ impl Verifier {
             &received_unrevealed_attrs,
             &received_self_attested_attrs,
             &received_predicates,
+            timestamp_tolerance,
         )?;
 
         let mut proof_verifier = CryptoVerifier::new_proof_verifier()?;
@@ -125,21 +218,38 @@ impl Verifier {
                     ))
                 })?);
 
-                let rev_regs_for_cred = rev_regs.get(&rev_reg_id).ok_or_else(|| {
-                    input_err(format!(
-                        "RevocationRegistry not found for id: {:?}",
-                        rev_reg_id
-                    ))
-                })?;
+                let rev_reg = if let Some(status_list) = rev_status_lists
+                    .and_then(|status_lists| status_lists.get(&rev_reg_id))
+                {
+                    if status_list.timestamp != timestamp {
+                        return Err(err_msg(
+                            IndyErrorKind::ProofRejected,
+                            format!(
+                                "RevocationStatusList timestamp {} does not match the proof's non-revocation timestamp {}",
+                                status_list.timestamp, timestamp
+                            ),
+                        ));
+                    }
+                    &status_list.registry
+                } else {
+                    let rev_regs_for_cred = rev_regs.get(&rev_reg_id).ok_or_else(|| {
+                        input_err(format!(
+                            "RevocationRegistry not found for id: {:?}",
+                            rev_reg_id
+                        ))
+                    })?;
 
-                let rev_reg = Some(rev_regs_for_cred.get(&timestamp).ok_or_else(|| {
-                    input_err(format!(
-                        "RevocationRegistry not found for timestamp: {:?}",
-                        timestamp
-                    ))
-                })?);
+                    match rev_regs_for_cred.get(&timestamp).ok_or_else(|| {
+                        input_err(format!(
+                            "RevocationRegistry not found for timestamp: {:?}",
+                            timestamp
+                        ))
+                    })? {
+                        RevocationRegistry::RevocationRegistryV1(reg) => &reg.value,
+                    }
+                };
 
-                (rev_reg_def, rev_reg)
+                (rev_reg_def, Some(rev_reg))
             } else {
                 (None, None)
             };
@@ -169,10 +279,6 @@ impl Verifier {
                     &reg_def.value.public_keys.accum_key
                 }
             });
-            let rev_reg = rev_reg.as_ref().map(|r_reg| match r_reg {
-                RevocationRegistry::RevocationRegistryV1(reg_def) => &reg_def.value,
-            });
-
             proof_verifier.add_sub_proof_request(
                 &sub_proof_request,
                 &credential_schema,
@@ -190,6 +296,296 @@ impl Verifier {
         Ok(valid)
     }
 
+    /// Evaluates every requested-attribute/predicate restriction against a
+    /// proof and collects every individual comparison failure rather than
+    /// stopping at the first one, so an integrator can surface precise,
+    /// multi-field feedback about why a proof was rejected. `verify_proof`
+    /// keeps the single-error short-circuit behavior as the fast default;
+    /// this is an additional, diagnostic-only entry point.
+    pub fn diagnose_requested_restrictions(
+        proof_req: &ProofRequest,
+        full_proof: &Proof,
+    ) -> IndyResult<RestrictionReport> {
+        let proof_req = proof_req.value();
+        let received_revealed_attrs = Self::_received_revealed_attrs(full_proof)?;
+        let received_unrevealed_attrs = Self::_received_unrevealed_attrs(full_proof)?;
+        let received_predicates = Self::_received_predicates(full_proof)?;
+        let received_self_attested_attrs = Self::_received_self_attested_attrs(full_proof);
+        let requested_proof = &full_proof.requested_proof;
+
+        let proof_attr_identifiers: HashMap<String, Identifier> = received_revealed_attrs
+            .iter()
+            .chain(received_unrevealed_attrs.iter())
+            .map(|(r, id)| (r.to_string(), id.clone()))
+            .collect();
+
+        let mut failures = Vec::new();
+
+        for (referent, info) in proof_req.requested_attributes.iter() {
+            if Self::_is_self_attested(referent, info, &received_self_attested_attrs) {
+                continue;
+            }
+            let query = match info.restrictions.as_ref() {
+                Some(query) => query,
+                None => continue,
+            };
+
+            let filter = Self::_gather_filter_info(referent, &proof_attr_identifiers)?;
+
+            let name_value_map: HashMap<String, Option<&str>> = if let Some(name) = &info.name {
+                let mut map = HashMap::new();
+                map.insert(
+                    name.clone(),
+                    requested_proof
+                        .revealed_attrs
+                        .get(referent)
+                        .map(|attr| attr.raw.as_str()),
+                );
+                map
+            } else if let Some(names) = &info.names {
+                let mut map = HashMap::new();
+                if let Some(attrs) = requested_proof.revealed_attr_groups.get(referent) {
+                    for name in names {
+                        map.insert(
+                            name.clone(),
+                            attrs.values.get(name).map(|attr| attr.raw.as_str()),
+                        );
+                    }
+                }
+                map
+            } else {
+                return Err(input_err(
+                    r#"Proof Request attribute restriction should contain "name" or "names" param"#,
+                ));
+            };
+
+            Self::_do_process_operator_collect(
+                &name_value_map,
+                query,
+                &filter,
+                referent,
+                &mut failures,
+            );
+        }
+
+        for (referent, info) in proof_req.requested_predicates.iter() {
+            let query = match info.restrictions.as_ref() {
+                Some(query) => query,
+                None => continue,
+            };
+
+            let filter = Self::_gather_filter_info(referent, &received_predicates)?;
+            let mut attr_value_map = HashMap::new();
+            attr_value_map.insert(info.name.clone(), None);
+
+            Self::_do_process_operator_collect(
+                &attr_value_map,
+                query,
+                &filter,
+                referent,
+                &mut failures,
+            );
+        }
+
+        Ok(RestrictionReport { failures })
+    }
+
+    /// Verify a W3C-format presentation against the same proof request used
+    /// for the legacy format: translate the presentation's per-credential
+    /// revealed attributes and predicate outcomes back into a synthetic
+    /// `Proof`/`RequestedProof`, then delegate entirely to `verify_proof` so
+    /// the cryptographic verification, restriction checks, and timestamp
+    /// checks are identical regardless of wire format.
+    pub fn verify_w3c_presentation(
+        presentation: &W3CPresentation,
+        proof_req: &ProofRequest,
+        schemas: &HashMap<SchemaId, &Schema>,
+        cred_defs: &HashMap<CredentialDefinitionId, &CredentialDefinition>,
+        rev_reg_defs: &HashMap<RevocationRegistryDefinitionId, &RevocationRegistryDefinition>,
+        rev_regs: &HashMap<RevocationRegistryDefinitionId, HashMap<u64, &RevocationRegistry>>,
+        rev_status_lists: Option<&HashMap<RevocationRegistryDefinitionId, &RevocationStatusList>>,
+        timestamp_tolerance: Option<u64>,
+        policy: Option<&VerificationPolicy>,
+    ) -> IndyResult<bool> {
+        trace!(
+            "verify_w3c_presentation >>> presentation: {:?}, proof_req: {:?}",
+            presentation,
+            proof_req
+        );
+
+        let full_proof = Self::_translate_w3c_presentation(presentation, proof_req.value())?;
+
+        let valid = Self::verify_proof(
+            &full_proof,
+            proof_req,
+            schemas,
+            cred_defs,
+            rev_reg_defs,
+            rev_regs,
+            rev_status_lists,
+            timestamp_tolerance,
+            policy,
+        )?;
+
+        trace!("verify_w3c_presentation <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    fn _translate_w3c_presentation(
+        presentation: &W3CPresentation,
+        proof_req: &ProofRequestPayload,
+    ) -> IndyResult<Proof> {
+        let identifiers = presentation
+            .credentials
+            .iter()
+            .map(|cred| Identifier {
+                schema_id: cred.schema_id.clone(),
+                cred_def_id: cred.cred_def_id.clone(),
+                rev_reg_id: cred.rev_reg_id.clone(),
+                timestamp: cred.timestamp,
+            })
+            .collect();
+
+        let mut requested_proof = RequestedProof::default();
+
+        for (referent, info) in proof_req.requested_attributes.iter() {
+            if let Some(name) = &info.name {
+                let (sub_proof_index, cred) =
+                    Self::_find_credential_with_attribute(&presentation.credentials, name)?;
+                let raw = match cred.attributes.get(name) {
+                    Some(CredentialAttributeValue::Attribute(raw)) => raw.clone(),
+                    _ => {
+                        return Err(input_err(format!(
+                            "Attribute \"{}\" for referent \"{}\" was not revealed",
+                            name, referent
+                        )))
+                    }
+                };
+                let encoded =
+                    Self::_revealed_attr_encoded(&presentation.proof, sub_proof_index, name)?;
+                requested_proof.revealed_attrs.insert(
+                    referent.clone(),
+                    RevealedAttributeInfo {
+                        sub_proof_index: sub_proof_index as u32,
+                        raw,
+                        encoded,
+                    },
+                );
+            } else if let Some(names) = &info.names {
+                let first = names.first().ok_or_else(|| {
+                    input_err(format!(
+                        "Attribute group restriction for referent \"{}\" has no names",
+                        referent
+                    ))
+                })?;
+                let (sub_proof_index, cred) =
+                    Self::_find_credential_with_attribute(&presentation.credentials, first)?;
+                let mut values = HashMap::new();
+                for name in names {
+                    let raw = match cred.attributes.get(name) {
+                        Some(CredentialAttributeValue::Attribute(raw)) => raw.clone(),
+                        _ => {
+                            return Err(input_err(format!(
+                                "Attribute \"{}\" for referent \"{}\" was not revealed",
+                                name, referent
+                            )))
+                        }
+                    };
+                    let encoded =
+                        Self::_revealed_attr_encoded(&presentation.proof, sub_proof_index, name)?;
+                    values.insert(name.clone(), AttributeValue { raw, encoded });
+                }
+                requested_proof.revealed_attr_groups.insert(
+                    referent.clone(),
+                    RevealedAttributeGroupInfo {
+                        sub_proof_index: sub_proof_index as u32,
+                        values,
+                    },
+                );
+            }
+        }
+
+        for (referent, info) in proof_req.requested_predicates.iter() {
+            let (sub_proof_index, _) =
+                Self::_find_credential_with_attribute(&presentation.credentials, &info.name)?;
+            requested_proof.predicates.insert(
+                referent.clone(),
+                SubProofReferent {
+                    sub_proof_index: sub_proof_index as u32,
+                },
+            );
+        }
+
+        Ok(Proof {
+            proof: presentation.proof.clone(),
+            requested_proof,
+            identifiers,
+        })
+    }
+
+    /// Finds the single credential in `credentials` that reveals `name`.
+    /// Nothing in `W3CCredentialPresentation` ties an attribute name back to
+    /// the proof-request referent that asked for it, so if more than one
+    /// credential reveals the same name (e.g. two referents each requesting
+    /// `name` from a different issuer) there is no way to tell which
+    /// credential a given referent means. Rather than silently picking the
+    /// first match — which would build the `Identifier`/restriction check
+    /// for a referent against the wrong sub-proof — this rejects the
+    /// presentation as ambiguous.
+    fn _find_credential_with_attribute<'p>(
+        credentials: &'p [W3CCredentialPresentation],
+        name: &str,
+    ) -> IndyResult<(usize, &'p W3CCredentialPresentation)> {
+        let mut matches = credentials
+            .iter()
+            .enumerate()
+            .filter(|(_, cred)| cred.attributes.contains_key(name));
+
+        let found = matches.next().ok_or_else(|| {
+            input_err(format!(
+                "Attribute \"{}\" not found in W3C presentation",
+                name
+            ))
+        })?;
+
+        if matches.next().is_some() {
+            return Err(input_err(format!(
+                "Attribute \"{}\" is revealed by more than one credential in the W3C \
+                 presentation; cannot unambiguously determine which one a referent means",
+                name
+            )));
+        }
+
+        Ok(found)
+    }
+
+    fn _revealed_attr_encoded(
+        proof: &CryptoProof,
+        sub_proof_index: usize,
+        attr_name: &str,
+    ) -> IndyResult<String> {
+        proof
+            .proofs
+            .get(sub_proof_index)
+            .ok_or_else(|| {
+                err_msg(
+                    IndyErrorKind::ProofRejected,
+                    format!("CryptoProof not found by index \"{}\"", sub_proof_index),
+                )
+            })?
+            .revealed_attrs()?
+            .iter()
+            .find(|(key, _)| attr_common_view(attr_name) == attr_common_view(&key))
+            .map(|(_, val)| val.to_string())
+            .ok_or_else(|| {
+                err_msg(
+                    IndyErrorKind::ProofRejected,
+                    format!("Attribute with name \"{}\" not found in CryptoProof", attr_name),
+                )
+            })
+    }
+
     pub fn generate_nonce(&self) -> IndyResult<Nonce> {
         trace!("generate_nonce >>> ");
 
@@ -315,6 +711,7 @@ impl Verifier {
         received_unrevealed_attrs: &HashMap<String, Identifier>,
         received_self_attested_attrs: &HashSet<String>,
         received_predicates: &HashMap<String, Identifier>,
+        timestamp_tolerance: Option<u64>,
     ) -> IndyResult<()> {
         proof_req
             .requested_attributes
@@ -325,6 +722,7 @@ impl Verifier {
                     referent,
                     &proof_req.non_revoked,
                     &info.non_revoked,
+                    timestamp_tolerance,
                 )
                 .or_else(|_| {
                     Self::_validate_timestamp(
@@ -332,6 +730,7 @@ impl Verifier {
                         referent,
                         &proof_req.non_revoked,
                         &info.non_revoked,
+                        timestamp_tolerance,
                     )
                 })
                 .or_else(|_| {
@@ -352,6 +751,7 @@ impl Verifier {
                     referent,
                     &proof_req.non_revoked,
                     &info.non_revoked,
+                    timestamp_tolerance,
                 )
             })
             .collect::<IndyResult<Vec<()>>>()?;
@@ -359,22 +759,53 @@ impl Verifier {
         Ok(())
     }
 
+    /// Resolve the effective non-revocation interval (local overrides global)
+    /// and reject unless the received `Identifier`'s timestamp falls within
+    /// it. `from` omitted means no lower bound; `to` is the "as of" upper
+    /// bound proof requests are built around. `timestamp_tolerance` widens
+    /// both bounds by a caller-supplied number of seconds to absorb clock
+    /// skew between the prover's ledger view and the verifier's.
     fn _validate_timestamp(
         received_: &HashMap<String, Identifier>,
         referent: &str,
         global_interval: &Option<NonRevocedInterval>,
         local_interval: &Option<NonRevocedInterval>,
+        timestamp_tolerance: Option<u64>,
     ) -> IndyResult<()> {
-        if get_non_revoc_interval(global_interval, local_interval).is_none() {
-            return Ok(());
-        }
+        let interval = match get_non_revoc_interval(global_interval, local_interval) {
+            Some(interval) => interval,
+            None => return Ok(()),
+        };
 
-        if !received_
+        let timestamp = received_
             .get(referent)
-            .map(|attr| attr.timestamp.is_some())
-            .unwrap_or(false)
-        {
-            return Err(input_err("Missing timestamp"));
+            .and_then(|attr| attr.timestamp)
+            .ok_or_else(|| input_err("Missing timestamp"))?;
+
+        let tolerance = timestamp_tolerance.unwrap_or(0);
+
+        if let Some(from) = interval.from {
+            if timestamp.saturating_add(tolerance) < from {
+                return Err(err_msg(
+                    IndyErrorKind::ProofRejected,
+                    format!(
+                        "Non-revocation timestamp {} is before the requested interval start {}",
+                        timestamp, from
+                    ),
+                ));
+            }
+        }
+
+        if let Some(to) = interval.to {
+            if timestamp > to.saturating_add(tolerance) {
+                return Err(err_msg(
+                    IndyErrorKind::ProofRejected,
+                    format!(
+                        "Non-revocation timestamp {} is after the requested interval end {}",
+                        timestamp, to
+                    ),
+                ));
+            }
         }
 
         Ok(())
@@ -514,6 +945,20 @@ impl Verifier {
         proof: &Proof,
         attr_info: &RevealedAttributeInfo,
     ) -> IndyResult<()> {
+        // Reuses `AttributeValues::encode` (the same canonical CL encoding
+        // credential issuance computes) rather than maintaining a second,
+        // independent implementation that could silently diverge from it.
+        let expected_encoded = AttributeValues::encode(&attr_info.raw);
+        if expected_encoded != attr_info.encoded {
+            return Err(err_msg(
+                IndyErrorKind::ProofRejected,
+                format!(
+                    "Encoded value \"{}\" for attribute \"{}\" is not the canonical encoding of raw value \"{}\" (expected \"{}\")",
+                    attr_info.encoded, attr_name, attr_info.raw, expected_encoded
+                ),
+            ));
+        }
+
         let reveal_attr_encoded = attr_info.encoded.to_string();
         let reveal_attr_encoded = Regex::new("^0*")
             .unwrap()
@@ -632,6 +1077,35 @@ impl Verifier {
         Ok(())
     }
 
+    /// Rejects self-attested values for any requested-attribute referent
+    /// the caller's `VerificationPolicy` does not permit. Referents that
+    /// were not satisfied by a self-attested value are unaffected here —
+    /// they still go through credential-backed restriction validation.
+    fn _enforce_self_attested_policy(
+        proof_req: &ProofRequestPayload,
+        self_attested_attrs: &HashSet<String>,
+        policy: Option<&VerificationPolicy>,
+    ) -> IndyResult<()> {
+        for referent in self_attested_attrs {
+            if !proof_req.requested_attributes.contains_key(referent) {
+                continue;
+            }
+            let permitted = policy
+                .map(|policy| policy.permits_self_attested(referent))
+                .unwrap_or(true);
+            if !permitted {
+                return Err(err_msg(
+                    IndyErrorKind::ProofRejected,
+                    format!(
+                        "Self-attested value for referent \"{}\" is not permitted by the verifier's policy",
+                        referent
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     fn _is_self_attested(
         referent: &str,
         info: &AttributeInfo,
@@ -678,6 +1152,7 @@ impl Verifier {
             schema_version,
             cred_def_id: identifier.cred_def_id.0.to_string(),
             issuer_did: issuer_did.0,
+            rev_reg_id: identifier.rev_reg_id.as_ref().map(|id| id.0.to_string()),
         })
     }
 
@@ -732,6 +1207,64 @@ impl Verifier {
                     ))
                 }
             }
+            Query::Ieq(ref tag_name, ref tag_value) => {
+                Self::_process_filter_matching(
+                    attr_value_map,
+                    &tag_name,
+                    &tag_value,
+                    filter,
+                    Self::_values_ieq,
+                )
+                .map_err(|err| {
+                    err.extend(format!(
+                        "$ieq operator validation failed for tag: \"{}\", value: \"{}\"",
+                        tag_name, tag_value
+                    ))
+                })
+            }
+            Query::Like(ref tag_name, ref pattern) => {
+                Self::_process_filter_matching(
+                    attr_value_map,
+                    &tag_name,
+                    &pattern,
+                    filter,
+                    Self::_values_like,
+                )
+                .map_err(|err| {
+                    err.extend(format!(
+                        "$like operator validation failed for tag: \"{}\", pattern: \"{}\"",
+                        tag_name, pattern
+                    ))
+                })
+            }
+            Query::Gt(ref tag_name, ref tag_value) => {
+                Self::_process_numeric_operator(attr_value_map, tag_name, tag_value, "$gt", |ord| {
+                    ord == Ordering::Greater
+                })
+            }
+            Query::Gte(ref tag_name, ref tag_value) => {
+                Self::_process_numeric_operator(
+                    attr_value_map,
+                    tag_name,
+                    tag_value,
+                    "$gte",
+                    |ord| ord != Ordering::Less,
+                )
+            }
+            Query::Lt(ref tag_name, ref tag_value) => {
+                Self::_process_numeric_operator(attr_value_map, tag_name, tag_value, "$lt", |ord| {
+                    ord == Ordering::Less
+                })
+            }
+            Query::Lte(ref tag_name, ref tag_value) => {
+                Self::_process_numeric_operator(
+                    attr_value_map,
+                    tag_name,
+                    tag_value,
+                    "$lte",
+                    |ord| ord != Ordering::Greater,
+                )
+            }
             Query::And(ref operators) => operators
                 .iter()
                 .map(|op| Self::_do_process_operator(attr_value_map, op, filter))
@@ -768,40 +1301,191 @@ impl Verifier {
         }
     }
 
+    /// The diagnostic counterpart to `_do_process_operator`: walks the
+    /// entire restriction tree instead of stopping at the first failure,
+    /// appending a `RestrictionFailure` record for every leaf comparison
+    /// that didn't hold. Returns whether the tree as a whole is satisfied.
+    fn _do_process_operator_collect(
+        attr_value_map: &HashMap<String, Option<&str>>,
+        restriction_op: &Query,
+        filter: &Filter,
+        referent: &str,
+        failures: &mut Vec<RestrictionFailure>,
+    ) -> bool {
+        match restriction_op {
+            Query::And(ref operators) => {
+                // Evaluate every branch (not short-circuiting on `&&`) so a
+                // failure in an earlier clause doesn't hide one in a later
+                // clause.
+                let mut all_ok = true;
+                for op in operators {
+                    let ok = Self::_do_process_operator_collect(
+                        attr_value_map,
+                        op,
+                        filter,
+                        referent,
+                        failures,
+                    );
+                    all_ok = all_ok && ok;
+                }
+                all_ok
+            }
+            Query::Or(ref operators) => {
+                let mut branch_failures = Vec::new();
+                let mut any_ok = false;
+                for op in operators {
+                    let mut sub_failures = Vec::new();
+                    if Self::_do_process_operator_collect(
+                        attr_value_map,
+                        op,
+                        filter,
+                        referent,
+                        &mut sub_failures,
+                    ) {
+                        any_ok = true;
+                    } else {
+                        branch_failures.extend(sub_failures);
+                    }
+                }
+                if !any_ok {
+                    failures.extend(branch_failures);
+                }
+                any_ok
+            }
+            Query::Not(ref operator) => {
+                let mut sub_failures = Vec::new();
+                let inner_ok = Self::_do_process_operator_collect(
+                    attr_value_map,
+                    &*operator,
+                    filter,
+                    referent,
+                    &mut sub_failures,
+                );
+                if inner_ok {
+                    failures.push(RestrictionFailure {
+                        referent: referent.to_string(),
+                        operator: "$not".to_string(),
+                        tag: String::new(),
+                        expected: "negated condition to fail".to_string(),
+                        actual: "negated condition passed".to_string(),
+                    });
+                }
+                !inner_ok
+            }
+            leaf => match Self::_do_process_operator(attr_value_map, leaf, filter) {
+                Ok(()) => true,
+                Err(err) => {
+                    let (tag, expected) = Self::_leaf_tag_and_expected(leaf);
+                    failures.push(RestrictionFailure {
+                        referent: referent.to_string(),
+                        operator: Self::_operator_name(leaf).to_string(),
+                        tag,
+                        expected,
+                        actual: err.to_string(),
+                    });
+                    false
+                }
+            },
+        }
+    }
+
+    fn _operator_name(op: &Query) -> &'static str {
+        match op {
+            Query::Eq(..) => "$eq",
+            Query::Neq(..) => "$neq",
+            Query::In(..) => "$in",
+            Query::Ieq(..) => "$ieq",
+            Query::Like(..) => "$like",
+            Query::Gt(..) => "$gt",
+            Query::Gte(..) => "$gte",
+            Query::Lt(..) => "$lt",
+            Query::Lte(..) => "$lte",
+            Query::And(..) => "$and",
+            Query::Or(..) => "$or",
+            Query::Not(..) => "$not",
+        }
+    }
+
+    fn _leaf_tag_and_expected(op: &Query) -> (String, String) {
+        match op {
+            Query::Eq(tag, value)
+            | Query::Neq(tag, value)
+            | Query::Ieq(tag, value)
+            | Query::Like(tag, value)
+            | Query::Gt(tag, value)
+            | Query::Gte(tag, value)
+            | Query::Lt(tag, value)
+            | Query::Lte(tag, value) => (tag.clone(), value.clone()),
+            Query::In(tag, values) => (tag.clone(), format!("{:?}", values)),
+            Query::And(..) | Query::Or(..) | Query::Not(..) => (String::new(), String::new()),
+        }
+    }
+
     fn _process_filter(
         attr_value_map: &HashMap<String, Option<&str>>,
         tag: &str,
         tag_value: &str,
         filter: &Filter,
+    ) -> IndyResult<()> {
+        Self::_process_filter_matching(attr_value_map, tag, tag_value, filter, Self::_values_eq)
+    }
+
+    fn _process_filter_matching(
+        attr_value_map: &HashMap<String, Option<&str>>,
+        tag: &str,
+        tag_value: &str,
+        filter: &Filter,
+        matches: fn(&str, &str) -> IndyResult<bool>,
     ) -> IndyResult<()> {
         trace!(
-            "_process_filter: attr_value_map: {:?}, tag: {}, tag_value: {}, filter: {:?}",
+            "_process_filter_matching: attr_value_map: {:?}, tag: {}, tag_value: {}, filter: {:?}",
             attr_value_map,
             tag,
             tag_value,
             filter
         );
         match tag {
-            tag_ @ "schema_id" => Self::_precess_filed(tag_, &filter.schema_id, tag_value),
+            tag_ @ "schema_id" => Self::_precess_filed(tag_, &filter.schema_id, tag_value, matches),
             tag_ @ "schema_issuer_did" => {
-                Self::_precess_filed(tag_, &filter.schema_issuer_did, tag_value)
+                Self::_precess_filed(tag_, &filter.schema_issuer_did, tag_value, matches)
+            }
+            tag_ @ "schema_name" => {
+                Self::_precess_filed(tag_, &filter.schema_name, tag_value, matches)
             }
-            tag_ @ "schema_name" => Self::_precess_filed(tag_, &filter.schema_name, tag_value),
             tag_ @ "schema_version" => {
-                Self::_precess_filed(tag_, &filter.schema_version, tag_value)
+                Self::_precess_filed(tag_, &filter.schema_version, tag_value, matches)
+            }
+            tag_ @ "cred_def_id" => {
+                Self::_precess_filed(tag_, &filter.cred_def_id, tag_value, matches)
             }
-            tag_ @ "cred_def_id" => Self::_precess_filed(tag_, &filter.cred_def_id, tag_value),
-            tag_ @ "issuer_did" => Self::_precess_filed(tag_, &filter.issuer_did, tag_value),
+            tag_ @ "issuer_did" => {
+                Self::_precess_filed(tag_, &filter.issuer_did, tag_value, matches)
+            }
+            tag_ @ "rev_reg_id" => match &filter.rev_reg_id {
+                Some(rev_reg_id) => Self::_precess_filed(tag_, rev_reg_id, tag_value, matches),
+                None => Err(err_msg(
+                    IndyErrorKind::ProofRejected,
+                    format!(
+                        "\"{}\" restriction requested but the credential has no revocation registry",
+                        tag_
+                    ),
+                )),
+            },
             x if Self::_is_attr_internal_tag(x, attr_value_map) => {
-                Self::_check_internal_tag_revealed_value(x, tag_value, attr_value_map)
+                Self::_check_internal_tag_revealed_value(x, tag_value, attr_value_map, matches)
             }
             x if Self::_is_attr_operator(x) => Ok(()),
             _ => Err(input_err("Unknown Filter Type")),
         }
     }
 
-    fn _precess_filed(filed: &str, filter_value: &str, tag_value: &str) -> IndyResult<()> {
-        if filter_value == tag_value {
+    fn _precess_filed(
+        filed: &str,
+        filter_value: &str,
+        tag_value: &str,
+        matches: fn(&str, &str) -> IndyResult<bool>,
+    ) -> IndyResult<()> {
+        if matches(filter_value, tag_value)? {
             Ok(())
         } else {
             Err(err_msg(
@@ -814,6 +1498,158 @@ impl Verifier {
         }
     }
 
+    fn _values_eq(filter_value: &str, tag_value: &str) -> IndyResult<bool> {
+        Ok(filter_value == tag_value)
+    }
+
+    fn _values_ieq(filter_value: &str, tag_value: &str) -> IndyResult<bool> {
+        Ok(filter_value.eq_ignore_ascii_case(tag_value))
+    }
+
+    fn _values_like(filter_value: &str, pattern: &str) -> IndyResult<bool> {
+        Ok(Self::_like_pattern_regex(pattern)?.is_match(filter_value))
+    }
+
+    /// Translates a SQL-style `$like` pattern (`%` = any run of characters,
+    /// `_` = any single character, `\%`/`\_` the escaped literal wildcards)
+    /// into an anchored regex, escaping any other regex metacharacters the
+    /// pattern happens to contain.
+    fn _like_pattern_regex(pattern: &str) -> IndyResult<Regex> {
+        if pattern.is_empty() {
+            return Err(input_err("\"$like\" pattern must not be empty"));
+        }
+        let mut regex_str = String::from("^");
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(escaped @ '%') | Some(escaped @ '_') => {
+                        regex_str.push_str(&regex::escape(&escaped.to_string()))
+                    }
+                    Some(other) => {
+                        return Err(input_err(format!(
+                            "Invalid \"$like\" pattern \"{}\": \"\\{}\" is not a recognized escape",
+                            pattern, other
+                        )))
+                    }
+                    None => {
+                        return Err(input_err(format!(
+                            "Invalid \"$like\" pattern \"{}\": trailing escape character",
+                            pattern
+                        )))
+                    }
+                },
+                '%' => regex_str.push_str(".*"),
+                '_' => regex_str.push('.'),
+                other => regex_str.push_str(&regex::escape(&other.to_string())),
+            }
+        }
+        regex_str.push('$');
+        Regex::new(&regex_str).map_err(|err| {
+            input_err(format!("Invalid \"$like\" pattern \"{}\": {}", pattern, err))
+        })
+    }
+
+    fn _process_numeric_operator(
+        attr_value_map: &HashMap<String, Option<&str>>,
+        tag: &str,
+        tag_value: &str,
+        op_name: &str,
+        accept: fn(Ordering) -> bool,
+    ) -> IndyResult<()> {
+        let candidate = Self::_numeric_candidate_value(tag, attr_value_map).map_err(|err| {
+            err.extend(format!(
+                "\"{}\" operator validation failed for tag: \"{}\"",
+                op_name, tag
+            ))
+        })?;
+        let ord = Self::_numeric_cmp(&candidate, tag_value).map_err(|err| {
+            err.extend(format!(
+                "\"{}\" operator validation failed for tag: \"{}\"",
+                op_name, tag
+            ))
+        })?;
+        if accept(ord) {
+            Ok(())
+        } else {
+            Err(err_msg(
+                IndyErrorKind::ProofRejected,
+                format!(
+                    "\"{}\" operator validation failed for tag: \"{}\": expected \"{}\", actual \"{}\"",
+                    op_name, tag, tag_value, candidate
+                ),
+            ))
+        }
+    }
+
+    /// Resolves the candidate value a `$gt`/`$gte`/`$lt`/`$lte` restriction
+    /// compares against: only a revealed-attribute's own value can be
+    /// compared numerically — the fixed metadata fields (schema_id,
+    /// cred_def_id, etc.) are opaque identifiers and are rejected.
+    fn _numeric_candidate_value(
+        tag: &str,
+        attr_value_map: &HashMap<String, Option<&str>>,
+    ) -> IndyResult<String> {
+        match tag {
+            "schema_id" | "schema_issuer_did" | "schema_name" | "schema_version"
+            | "cred_def_id" | "issuer_did" | "rev_reg_id" => Err(input_err(format!(
+                "\"{}\" is an opaque identifier and does not support numeric comparison",
+                tag
+            ))),
+            x if Self::_is_attr_internal_tag(x, attr_value_map) => INTERNAL_TAG_MATCHER
+                .captures(x)
+                .and_then(|caps| caps.get(1))
+                .and_then(|name| attr_value_map.get(name.as_str()))
+                .and_then(|value| *value)
+                .map(str::to_string)
+                .ok_or_else(|| input_err(format!("No revealed value found for tag \"{}\"", tag))),
+            x if Self::_is_attr_operator(x) => Err(input_err(format!(
+                "\"{}\" does not carry a value usable for numeric comparison",
+                tag
+            ))),
+            _ => Err(input_err("Unknown Filter Type")),
+        }
+    }
+
+    fn _numeric_cmp(candidate: &str, tag_value: &str) -> IndyResult<Ordering> {
+        match (candidate.parse::<i64>(), tag_value.parse::<i64>()) {
+            (Ok(a), Ok(b)) => Ok(a.cmp(&b)),
+            _ => Self::_decimal_cmp(candidate, tag_value),
+        }
+    }
+
+    /// Arbitrary-precision decimal comparison used as a fallback when an
+    /// operand overflows `i64`: validates both operands are signed decimal
+    /// integers and compares them without ever materializing a bignum type.
+    fn _decimal_cmp(a: &str, b: &str) -> IndyResult<Ordering> {
+        let (a_neg, a_digits) = Self::_signed_digits(a)?;
+        let (b_neg, b_digits) = Self::_signed_digits(b)?;
+        Ok(match (a_neg, b_neg) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => Self::_cmp_digits(&a_digits, &b_digits),
+            (true, true) => Self::_cmp_digits(&b_digits, &a_digits),
+        })
+    }
+
+    fn _signed_digits(value: &str) -> IndyResult<(bool, &str)> {
+        let (neg, digits) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(err_msg(
+                IndyErrorKind::ProofRejected,
+                format!("\"{}\" is not a numeric value", value),
+            ));
+        }
+        Ok((neg, digits.trim_start_matches('0')))
+    }
+
+    fn _cmp_digits(a: &str, b: &str) -> Ordering {
+        a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+    }
+
     fn _is_attr_internal_tag(key: &str, attr_value_map: &HashMap<String, Option<&str>>) -> bool {
         INTERNAL_TAG_MATCHER
             .captures(key)
@@ -829,6 +1665,7 @@ impl Verifier {
         key: &str,
         tag_value: &str,
         attr_value_map: &HashMap<String, Option<&str>>,
+        matches: fn(&str, &str) -> IndyResult<bool>,
     ) -> IndyResult<()> {
         let attr_name = INTERNAL_TAG_MATCHER
             .captures(key)
@@ -843,7 +1680,7 @@ impl Verifier {
             ))?
             .as_str();
         if let Some(Some(revealed_value)) = attr_value_map.get(attr_name) {
-            if *revealed_value != tag_value {
+            if !matches(revealed_value, tag_value)? {
                 return Err(err_msg(
                     IndyErrorKind::ProofRejected,
                     format!(
@@ -871,6 +1708,11 @@ mod tests {
     pub const SCHEMA_VERSION: &str = "1.2.3";
     pub const CRED_DEF_ID: &str = "345";
     pub const ISSUER_DID: &str = "456";
+    pub const REV_REG_ID: &str = "567";
+
+    fn rev_reg_id_tag() -> String {
+        "rev_reg_id".to_string()
+    }
 
     fn schema_id_tag() -> String {
         "schema_id".to_string()
@@ -916,6 +1758,85 @@ mod tests {
             schema_version: SCHEMA_VERSION.to_string(),
             cred_def_id: CRED_DEF_ID.to_string(),
             issuer_did: ISSUER_DID.to_string(),
+            rev_reg_id: Some(REV_REG_ID.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_process_operator_eq_for_rev_reg_id() {
+        let mut op = Query::Eq(rev_reg_id_tag(), REV_REG_ID.to_string());
+        Verifier::_do_process_operator(&HashMap::new(), &op, &filter()).unwrap();
+
+        op = Query::Eq(rev_reg_id_tag(), "Not Here".to_string());
+        assert!(Verifier::_do_process_operator(&HashMap::new(), &op, &filter()).is_err());
+    }
+
+    #[test]
+    fn test_process_operator_eq_for_missing_rev_reg_id() {
+        let mut no_rev_reg_filter = filter();
+        no_rev_reg_filter.rev_reg_id = None;
+        let op = Query::Eq(rev_reg_id_tag(), REV_REG_ID.to_string());
+        assert!(Verifier::_do_process_operator(&HashMap::new(), &op, &no_rev_reg_filter).is_err());
+    }
+
+    /// Guards against `_do_process_operator` and
+    /// `domain::proof_request::matches_restriction` silently diverging on
+    /// the operators they both support: both are run against an identical
+    /// credential (same `Filter`/`attr_value_map` and `tags`, see the doc
+    /// comment on `Filter`) for a battery of restrictions and must agree on
+    /// every one.
+    #[test]
+    fn do_process_operator_agrees_with_matches_restriction() {
+        use crate::domain::proof_request::matches_restriction;
+
+        let mut attr_value_map = HashMap::new();
+        attr_value_map.insert("zip".to_string(), Some("12345"));
+
+        let mut tags = HashMap::new();
+        tags.insert("schema_id".to_string(), SCHEMA_ID.to_string());
+        tags.insert("schema_name".to_string(), SCHEMA_NAME.to_string());
+        tags.insert("schema_issuer_did".to_string(), SCHEMA_ISSUER_DID.to_string());
+        tags.insert("schema_version".to_string(), SCHEMA_VERSION.to_string());
+        tags.insert("cred_def_id".to_string(), CRED_DEF_ID.to_string());
+        tags.insert("issuer_did".to_string(), ISSUER_DID.to_string());
+        tags.insert("rev_reg_id".to_string(), REV_REG_ID.to_string());
+        tags.insert("attr::zip::marker".to_string(), "1".to_string());
+        tags.insert("attr::zip::value".to_string(), "12345".to_string());
+
+        let cases = vec![
+            Query::Eq(schema_id_tag(), SCHEMA_ID.to_string()),
+            Query::Eq(schema_id_tag(), "other".to_string()),
+            Query::Neq(cred_def_id_tag(), "other".to_string()),
+            Query::Neq(cred_def_id_tag(), CRED_DEF_ID.to_string()),
+            Query::In(
+                issuer_did_tag(),
+                vec!["nope".to_string(), ISSUER_DID.to_string()],
+            ),
+            Query::In(issuer_did_tag(), vec!["nope".to_string()]),
+            Query::Eq(rev_reg_id_tag(), REV_REG_ID.to_string()),
+            Query::Eq(rev_reg_id_tag(), "other".to_string()),
+            Query::Gte(attr_tag_value(), "100".to_string()),
+            Query::Lt(attr_tag_value(), "100".to_string()),
+            Query::And(vec![
+                Query::Eq(schema_id_tag(), SCHEMA_ID.to_string()),
+                Query::Eq(issuer_did_tag(), ISSUER_DID.to_string()),
+            ]),
+            Query::Or(vec![
+                Query::Eq(schema_id_tag(), "other".to_string()),
+                Query::Eq(issuer_did_tag(), ISSUER_DID.to_string()),
+            ]),
+            Query::Not(Box::new(Query::Eq(schema_id_tag(), "other".to_string()))),
+        ];
+
+        for query in cases {
+            let verifier_result =
+                Verifier::_do_process_operator(&attr_value_map, &query, &filter()).is_ok();
+            let prover_result = matches_restriction(&query, &tags);
+            assert_eq!(
+                verifier_result, prover_result,
+                "evaluators disagree on {:?}",
+                query
+            );
         }
     }
 
@@ -965,6 +1886,139 @@ mod tests {
         Verifier::_process_operator("zip", &op, &filter, None).unwrap()
     }
 
+    #[test]
+    fn test_process_op_like() {
+        let filter = filter();
+
+        Verifier::_process_operator(
+            "zip",
+            &Query::Like(issuer_did_tag(), "45%".to_string()),
+            &filter,
+            None,
+        )
+        .unwrap();
+        assert!(Verifier::_process_operator(
+            "zip",
+            &Query::Like(issuer_did_tag(), "99%".to_string()),
+            &filter,
+            None,
+        )
+        .is_err());
+
+        Verifier::_process_operator(
+            "zip",
+            &Query::Like(issuer_did_tag(), "4_6".to_string()),
+            &filter,
+            None,
+        )
+        .unwrap();
+
+        // literal wildcards via the `\%`/`\_` escapes
+        Verifier::_process_operator(
+            "zip",
+            &Query::Like(attr_tag_value(), "50\\%".to_string()),
+            &filter,
+            Some("50%"),
+        )
+        .unwrap();
+        assert!(Verifier::_process_operator(
+            "zip",
+            &Query::Like(attr_tag_value(), "50\\%".to_string()),
+            &filter,
+            Some("50x"),
+        )
+        .is_err());
+
+        assert!(Verifier::_process_operator(
+            "zip",
+            &Query::Like(issuer_did_tag(), "".to_string()),
+            &filter,
+            None,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_process_op_numeric_comparisons() {
+        let filter = filter();
+
+        Verifier::_process_operator(
+            "zip",
+            &Query::Gt(attr_tag_value(), "17".to_string()),
+            &filter,
+            Some("18"),
+        )
+        .unwrap();
+        assert!(Verifier::_process_operator(
+            "zip",
+            &Query::Gt(attr_tag_value(), "18".to_string()),
+            &filter,
+            Some("18"),
+        )
+        .is_err());
+
+        Verifier::_process_operator(
+            "zip",
+            &Query::Gte(attr_tag_value(), "18".to_string()),
+            &filter,
+            Some("18"),
+        )
+        .unwrap();
+
+        Verifier::_process_operator(
+            "zip",
+            &Query::Lt(attr_tag_value(), "19".to_string()),
+            &filter,
+            Some("18"),
+        )
+        .unwrap();
+        assert!(Verifier::_process_operator(
+            "zip",
+            &Query::Lt(attr_tag_value(), "18".to_string()),
+            &filter,
+            Some("18"),
+        )
+        .is_err());
+
+        Verifier::_process_operator(
+            "zip",
+            &Query::Lte(attr_tag_value(), "18".to_string()),
+            &filter,
+            Some("18"),
+        )
+        .unwrap();
+
+        // arbitrary-precision fallback once an operand overflows i64
+        Verifier::_process_operator(
+            "zip",
+            &Query::Gt(
+                attr_tag_value(),
+                "99999999999999999999999999999999".to_string(),
+            ),
+            &filter,
+            Some("100000000000000000000000000000000"),
+        )
+        .unwrap();
+
+        // opaque metadata fields are not numerically comparable
+        assert!(Verifier::_process_operator(
+            "zip",
+            &Query::Gt(cred_def_id_tag(), "1".to_string()),
+            &filter,
+            None,
+        )
+        .is_err());
+
+        // non-numeric revealed values are rejected, not silently accepted
+        assert!(Verifier::_process_operator(
+            "zip",
+            &Query::Gt(attr_tag_value(), "not a number".to_string()),
+            &filter,
+            Some("18"),
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_process_op_or() {
         let filter = filter();
@@ -1019,6 +2073,50 @@ mod tests {
         Verifier::_process_operator("zip", &op, &filter, None).unwrap()
     }
 
+    #[test]
+    fn test_process_op_collect_gathers_every_and_branch_failure() {
+        let filter = filter();
+        let mut attr_value_map = HashMap::new();
+        attr_value_map.insert("zip".to_string(), None);
+
+        let op = Query::And(vec![
+            Query::Eq(schema_id_tag(), "Not Here".to_string()),
+            Query::Eq(cred_def_id_tag(), "Not Here Either".to_string()),
+        ]);
+        let mut failures = Vec::new();
+        let accepted = Verifier::_do_process_operator_collect(
+            &attr_value_map,
+            &op,
+            &filter,
+            "referent",
+            &mut failures,
+        );
+        assert!(!accepted);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].tag, "schema_id");
+        assert_eq!(failures[1].tag, "cred_def_id");
+        assert!(failures.iter().all(|f| f.referent == "referent"));
+    }
+
+    #[test]
+    fn test_process_op_collect_accepts_when_satisfied() {
+        let filter = filter();
+        let mut attr_value_map = HashMap::new();
+        attr_value_map.insert("zip".to_string(), None);
+
+        let op = Query::Eq(schema_id_tag(), SCHEMA_ID.to_string());
+        let mut failures = Vec::new();
+        let accepted = Verifier::_do_process_operator_collect(
+            &attr_value_map,
+            &op,
+            &filter,
+            "referent",
+            &mut failures,
+        );
+        assert!(accepted);
+        assert!(failures.is_empty());
+    }
+
     #[test]
     fn test_proccess_op_or_with_nested_and() {
         let filter = filter();
@@ -1169,7 +2267,7 @@ mod tests {
                 timestamp: Some(1234),
                 schema_id: SchemaId(String::new()),
                 cred_def_id: CredentialDefinitionId(String::new()),
-                rev_reg_id: Some(RevocationRegistryId(String::new())),
+                rev_reg_id: Some(RevocationRegistryDefinitionId(String::new())),
             },
         );
         res.insert(
@@ -1178,7 +2276,7 @@ mod tests {
                 timestamp: None,
                 schema_id: SchemaId(String::new()),
                 cred_def_id: CredentialDefinitionId(String::new()),
-                rev_reg_id: Some(RevocationRegistryId(String::new())),
+                rev_reg_id: Some(RevocationRegistryDefinitionId(String::new())),
             },
         );
         res
@@ -1193,20 +2291,216 @@ mod tests {
 
     #[test]
     fn validate_timestamp_works() {
-        Verifier::_validate_timestamp(&_received(), "referent_1", &None, &None).unwrap();
-        Verifier::_validate_timestamp(&_received(), "referent_1", &Some(_interval()), &None)
+        Verifier::_validate_timestamp(&_received(), "referent_1", &None, &None, None).unwrap();
+        Verifier::_validate_timestamp(&_received(), "referent_1", &Some(_interval()), &None, None)
             .unwrap();
-        Verifier::_validate_timestamp(&_received(), "referent_1", &None, &Some(_interval()))
+        Verifier::_validate_timestamp(&_received(), "referent_1", &None, &Some(_interval()), None)
             .unwrap();
     }
 
     #[test]
     fn validate_timestamp_not_work() {
-        Verifier::_validate_timestamp(&_received(), "referent_2", &Some(_interval()), &None)
+        Verifier::_validate_timestamp(&_received(), "referent_2", &Some(_interval()), &None, None)
+            .unwrap_err();
+        Verifier::_validate_timestamp(&_received(), "referent_2", &None, &Some(_interval()), None)
             .unwrap_err();
-        Verifier::_validate_timestamp(&_received(), "referent_2", &None, &Some(_interval()))
+        Verifier::_validate_timestamp(&_received(), "referent_3", &None, &Some(_interval()), None)
             .unwrap_err();
-        Verifier::_validate_timestamp(&_received(), "referent_3", &None, &Some(_interval()))
+    }
+
+    #[test]
+    fn validate_timestamp_enforces_interval_bounds() {
+        let interval = NonRevocedInterval {
+            from: Some(1235),
+            to: Some(2000),
+        };
+        Verifier::_validate_timestamp(&_received(), "referent_1", &None, &Some(interval), None)
+            .unwrap_err();
+        Verifier::_validate_timestamp(&_received(), "referent_1", &None, &Some(interval), Some(5))
+            .unwrap();
+    }
+
+    #[test]
+    fn validate_timestamp_enforces_upper_bound_with_leeway() {
+        let interval = NonRevocedInterval {
+            from: None,
+            to: Some(1000),
+        };
+        Verifier::_validate_timestamp(&_received(), "referent_1", &None, &Some(interval), None)
             .unwrap_err();
+        Verifier::_validate_timestamp(
+            &_received(),
+            "referent_1",
+            &None,
+            &Some(interval),
+            Some(234),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn validate_timestamp_local_interval_overrides_global() {
+        // `referent_1`'s received timestamp is 1234 (see `_received`). A
+        // global interval that would reject it must be overridden by a
+        // looser per-referent interval, and vice versa.
+        let permissive = NonRevocedInterval {
+            from: None,
+            to: Some(2000),
+        };
+        let restrictive = NonRevocedInterval {
+            from: None,
+            to: Some(1000),
+        };
+
+        Verifier::_validate_timestamp(
+            &_received(),
+            "referent_1",
+            &Some(restrictive),
+            &Some(permissive),
+            None,
+        )
+        .unwrap();
+
+        Verifier::_validate_timestamp(
+            &_received(),
+            "referent_1",
+            &Some(permissive),
+            &Some(restrictive),
+            None,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn validate_timestamp_tolerance_does_not_overflow_near_u64_max() {
+        let mut received = HashMap::new();
+        received.insert(
+            "referent_1".to_string(),
+            Identifier {
+                timestamp: Some(u64::MAX - 1),
+                schema_id: SchemaId(String::new()),
+                cred_def_id: CredentialDefinitionId(String::new()),
+                rev_reg_id: Some(RevocationRegistryDefinitionId(String::new())),
+            },
+        );
+        let interval = NonRevocedInterval {
+            from: None,
+            to: Some(u64::MAX - 1),
+        };
+        Verifier::_validate_timestamp(
+            &received,
+            "referent_1",
+            &None,
+            &Some(interval),
+            Some(u64::MAX),
+        )
+        .unwrap();
+    }
+
+    fn _proof_req_with_attr_referent(referent: &str) -> ProofRequestPayload {
+        let mut requested_attributes = HashMap::new();
+        requested_attributes.insert(
+            referent.to_string(),
+            AttributeInfo {
+                name: Some("name".to_string()),
+                names: None,
+                restrictions: None,
+                non_revoked: None,
+            },
+        );
+        ProofRequestPayload {
+            nonce: new_nonce().unwrap(),
+            name: "proof_req".to_string(),
+            version: "1.0".to_string(),
+            requested_attributes,
+            requested_predicates: HashMap::new(),
+            non_revoked: None,
+        }
+    }
+
+    #[test]
+    fn enforce_self_attested_policy_default_allows_self_attestation() {
+        let proof_req = _proof_req_with_attr_referent("attr_referent");
+        let mut self_attested = HashSet::new();
+        self_attested.insert("attr_referent".to_string());
+
+        Verifier::_enforce_self_attested_policy(&proof_req, &self_attested, None).unwrap();
+        Verifier::_enforce_self_attested_policy(
+            &proof_req,
+            &self_attested,
+            Some(&VerificationPolicy::default()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn enforce_self_attested_policy_can_forbid_globally() {
+        let proof_req = _proof_req_with_attr_referent("attr_referent");
+        let mut self_attested = HashSet::new();
+        self_attested.insert("attr_referent".to_string());
+
+        let policy = VerificationPolicy {
+            allow_self_attested: false,
+            self_attested_denylist: HashSet::new(),
+        };
+        assert!(
+            Verifier::_enforce_self_attested_policy(&proof_req, &self_attested, Some(&policy))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn enforce_self_attested_policy_can_forbid_by_referent() {
+        let proof_req = _proof_req_with_attr_referent("attr_referent");
+        let mut self_attested = HashSet::new();
+        self_attested.insert("attr_referent".to_string());
+
+        let mut denylist = HashSet::new();
+        denylist.insert("attr_referent".to_string());
+        let policy = VerificationPolicy {
+            allow_self_attested: true,
+            self_attested_denylist: denylist,
+        };
+        assert!(
+            Verifier::_enforce_self_attested_policy(&proof_req, &self_attested, Some(&policy))
+                .is_err()
+        );
+
+        // a referent not present in the proof's self-attested set is unaffected
+        let other_proof_req = _proof_req_with_attr_referent("other_referent");
+        Verifier::_enforce_self_attested_policy(&other_proof_req, &self_attested, Some(&policy))
+            .unwrap();
+    }
+
+    fn _w3c_credential(name: &str, raw: &str) -> W3CCredentialPresentation {
+        let mut attributes = HashMap::new();
+        attributes.insert(name.to_string(), CredentialAttributeValue::Attribute(raw.to_string()));
+        W3CCredentialPresentation {
+            schema_id: SchemaId(String::new()),
+            cred_def_id: CredentialDefinitionId(String::new()),
+            rev_reg_id: None,
+            timestamp: None,
+            attributes,
+        }
+    }
+
+    #[test]
+    fn find_credential_with_attribute_rejects_duplicate_name_across_credentials() {
+        let credentials = vec![
+            _w3c_credential("name", "Alice"),
+            _w3c_credential("name", "Bob"),
+        ];
+
+        assert!(Verifier::_find_credential_with_attribute(&credentials, "name").is_err());
+    }
+
+    #[test]
+    fn find_credential_with_attribute_finds_unique_name() {
+        let credentials = vec![_w3c_credential("name", "Alice"), _w3c_credential("age", "25")];
+
+        let (sub_proof_index, cred) =
+            Verifier::_find_credential_with_attribute(&credentials, "age").unwrap();
+        assert_eq!(sub_proof_index, 1);
+        assert!(cred.attributes.contains_key("age"));
     }
 }
@@ -0,0 +1,61 @@
+pub fn combine(prefix: &str, method: Option<&str>, entity: &str) -> String {
+    match method {
+        Some(method) => [prefix, method, entity].join(":"),
+        None => entity.to_owned(),
+    }
+}
+
+pub fn method_from_prefix<'a>(prefix: &str, value: &'a str) -> Option<&'a str> {
+    let lead = format!("{}:", prefix);
+    if !value.starts_with(&lead) {
+        return None;
+    }
+    value[lead.len()..].split_terminator(':').next()
+}
+
+pub trait Qualifiable: Sized {
+    fn prefix() -> &'static str;
+    fn combine(method: Option<&str>, entity: &str) -> Self;
+    fn to_unqualified(&self) -> Self;
+}
+
+#[macro_export]
+macro_rules! qualifiable_type {
+    ($i:ident) => {
+        #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+        pub struct $i(pub String);
+
+        impl $i {
+            pub fn as_str(&self) -> &str {
+                self.0.as_str()
+            }
+
+            pub fn get_method(&self) -> Option<&str> {
+                $crate::utils::qualifier::method_from_prefix(
+                    <Self as $crate::utils::qualifier::Qualifiable>::prefix(),
+                    &self.0,
+                )
+            }
+
+            pub fn default_method(&self, method: Option<&str>) -> Self {
+                if self.get_method().is_none() {
+                    <Self as $crate::utils::qualifier::Qualifiable>::combine(method, &self.0)
+                } else {
+                    self.clone()
+                }
+            }
+        }
+
+        impl From<String> for $i {
+            fn from(value: String) -> Self {
+                Self(value)
+            }
+        }
+
+        impl std::fmt::Display for $i {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
@@ -0,0 +1,153 @@
+use serde::de::{Deserialize, Deserializer, Error as DeError};
+use serde::ser::{Serialize, Serializer};
+use serde_json::Value;
+
+use crate::common::error::prelude::*;
+
+/// A restriction query tree, matching the subset of WQL (Wallet Query
+/// Language) that proof-request `restrictions` are expressed in: tag
+/// equality/inequality, set membership, a case-insensitive equality mode,
+/// a `$like` pattern match, the `$gt`/`$gte`/`$lt`/`$lte` numeric
+/// comparisons, and the `$and`/`$or`/`$not` combinators.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    Eq(String, String),
+    Neq(String, String),
+    In(String, Vec<String>),
+    Ieq(String, String),
+    Like(String, String),
+    Gt(String, String),
+    Gte(String, String),
+    Lt(String, String),
+    Lte(String, String),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+    Not(Box<Query>),
+}
+
+impl Query {
+    fn from_value(value: &Value) -> IndyResult<Query> {
+        let map = value
+            .as_object()
+            .ok_or_else(|| input_err("WQL restriction must be a JSON object"))?;
+
+        if map.len() == 1 {
+            let (key, val) = map.iter().next().unwrap();
+            return match key.as_str() {
+                "$and" => Ok(Query::And(Self::_parse_array(val)?)),
+                "$or" => Ok(Query::Or(Self::_parse_array(val)?)),
+                "$not" => Ok(Query::Not(Box::new(Self::from_value(val)?))),
+                tag => Self::_parse_tag_value(tag, val),
+            };
+        }
+
+        let clauses = map
+            .iter()
+            .map(|(tag, val)| Self::_parse_tag_value(tag, val))
+            .collect::<IndyResult<Vec<Query>>>()?;
+        Ok(Query::And(clauses))
+    }
+
+    fn _parse_array(value: &Value) -> IndyResult<Vec<Query>> {
+        value
+            .as_array()
+            .ok_or_else(|| input_err("Expected an array of WQL restrictions"))?
+            .iter()
+            .map(Self::from_value)
+            .collect()
+    }
+
+    fn _parse_tag_value(tag: &str, value: &Value) -> IndyResult<Query> {
+        match value {
+            Value::String(s) => Ok(Query::Eq(tag.to_string(), s.clone())),
+            Value::Object(map) => {
+                if map.len() != 1 {
+                    return Err(input_err(format!(
+                        "Invalid WQL restriction for tag \"{}\": expected a single operator",
+                        tag
+                    )));
+                }
+                let (op, val) = map.iter().next().unwrap();
+                match op.as_str() {
+                    "$eq" => Ok(Query::Eq(tag.to_string(), Self::_expect_str(tag, val)?)),
+                    "$neq" => Ok(Query::Neq(tag.to_string(), Self::_expect_str(tag, val)?)),
+                    "$ieq" => Ok(Query::Ieq(tag.to_string(), Self::_expect_str(tag, val)?)),
+                    "$like" => Ok(Query::Like(tag.to_string(), Self::_expect_str(tag, val)?)),
+                    "$gt" => Ok(Query::Gt(tag.to_string(), Self::_expect_str(tag, val)?)),
+                    "$gte" => Ok(Query::Gte(tag.to_string(), Self::_expect_str(tag, val)?)),
+                    "$lt" => Ok(Query::Lt(tag.to_string(), Self::_expect_str(tag, val)?)),
+                    "$lte" => Ok(Query::Lte(tag.to_string(), Self::_expect_str(tag, val)?)),
+                    "$in" => {
+                        let values = val
+                            .as_array()
+                            .ok_or_else(|| {
+                                input_err(format!(
+                                    "\"$in\" value for tag \"{}\" must be an array",
+                                    tag
+                                ))
+                            })?
+                            .iter()
+                            .map(|v| Self::_expect_str(tag, v))
+                            .collect::<IndyResult<Vec<String>>>()?;
+                        Ok(Query::In(tag.to_string(), values))
+                    }
+                    _ => Err(input_err(format!(
+                        "Unsupported WQL operator \"{}\" for tag \"{}\"",
+                        op, tag
+                    ))),
+                }
+            }
+            _ => Err(input_err(format!(
+                "Invalid WQL restriction value for tag \"{}\"",
+                tag
+            ))),
+        }
+    }
+
+    fn _expect_str(tag: &str, value: &Value) -> IndyResult<String> {
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| input_err(format!("Expected a string value for tag \"{}\"", tag)))
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            Query::Eq(tag, value) => json!({ tag: value }),
+            Query::Neq(tag, value) => json!({ tag: { "$neq": value } }),
+            Query::In(tag, values) => json!({ tag: { "$in": values } }),
+            Query::Ieq(tag, value) => json!({ tag: { "$ieq": value } }),
+            Query::Like(tag, pattern) => json!({ tag: { "$like": pattern } }),
+            Query::Gt(tag, value) => json!({ tag: { "$gt": value } }),
+            Query::Gte(tag, value) => json!({ tag: { "$gte": value } }),
+            Query::Lt(tag, value) => json!({ tag: { "$lt": value } }),
+            Query::Lte(tag, value) => json!({ tag: { "$lte": value } }),
+            Query::And(clauses) => {
+                json!({ "$and": clauses.iter().map(Query::to_value).collect::<Vec<_>>() })
+            }
+            Query::Or(clauses) => {
+                json!({ "$or": clauses.iter().map(Query::to_value).collect::<Vec<_>>() })
+            }
+            Query::Not(clause) => json!({ "$not": clause.to_value() }),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Query {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        Query::from_value(&value).map_err(DeError::custom)
+    }
+}
+
+impl Serialize for Query {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_value().serialize(serializer)
+    }
+}
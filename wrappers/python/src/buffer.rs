@@ -12,9 +12,19 @@ use zeroize::Zeroize;
 
 use indy_credx::common::error::IndyResult;
 
+/// Which codec produced a buffer's bytes, so `deserialize` can round-trip a
+/// buffer regardless of whether it was built via [`PySafeBuffer::serialize`]
+/// or [`PySafeBuffer::serialize_cbor`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BufferFormat {
+    Json,
+    Cbor,
+}
+
 #[pyclass(name=SafeBuffer)]
 pub struct PySafeBuffer {
     inner: Vec<u8>,
+    format: BufferFormat,
 }
 
 #[pyproto]
@@ -84,7 +94,10 @@ impl PyObjectProtocol for PySafeBuffer {
 
 impl PySafeBuffer {
     pub fn new(buf: Vec<u8>) -> Self {
-        Self { inner: buf }
+        Self {
+            inner: buf,
+            format: BufferFormat::Json,
+        }
     }
 
     pub fn serialize<T>(value: &T) -> IndyResult<Self>
@@ -99,7 +112,10 @@ impl PySafeBuffer {
     where
         T: serde::de::DeserializeOwned,
     {
-        let result = serde_json::from_slice::<T>(self.inner.as_slice())?;
+        let result = match self.format {
+            BufferFormat::Json => serde_json::from_slice::<T>(self.inner.as_slice())?,
+            BufferFormat::Cbor => serde_cbor::from_slice::<T>(self.inner.as_slice())?,
+        };
         Ok(result)
     }
 
@@ -111,8 +127,63 @@ impl PySafeBuffer {
         Self::serialize(&value)
     }
 
-    pub fn to_json_insecure<T>(&self) -> IndyResult<String> {
-        Ok(String::from_utf8_lossy(&self.inner).to_string())
+    pub fn to_json_insecure<T>(&self) -> IndyResult<String>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        match self.format {
+            BufferFormat::Json => Ok(String::from_utf8_lossy(&self.inner).to_string()),
+            BufferFormat::Cbor => {
+                let value = self.deserialize::<T>()?;
+                Ok(serde_json::to_string(&value)?)
+            }
+        }
+    }
+
+    /// Serialize into the compact CBOR representation instead of JSON, which
+    /// matters for the large CL key material (`CredentialPrivateKey`,
+    /// `RevocationPrivateKey`, `MasterSecret`) that otherwise pays the bulkier
+    /// JSON encoding on every round-trip to disk or across a wire.
+    pub fn serialize_cbor<T>(value: &T) -> IndyResult<Self>
+    where
+        T: serde::Serialize,
+    {
+        let cbor = serde_cbor::to_vec(value)?;
+        Ok(Self {
+            inner: cbor,
+            format: BufferFormat::Cbor,
+        })
+    }
+
+    pub fn deserialize_cbor<T>(&self) -> IndyResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let result = serde_cbor::from_slice::<T>(self.inner.as_slice())?;
+        Ok(result)
+    }
+
+    /// Raw CBOR bytes for this buffer's value, re-encoding from JSON first if
+    /// that's how the buffer was originally built.
+    pub fn to_cbor_insecure<T>(&self) -> IndyResult<Vec<u8>>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        match self.format {
+            BufferFormat::Cbor => Ok(self.inner.clone()),
+            BufferFormat::Json => {
+                let value = self.deserialize::<T>()?;
+                Ok(serde_cbor::to_vec(&value)?)
+            }
+        }
+    }
+
+    pub fn from_cbor_insecure<T>(cbor: &[u8]) -> IndyResult<Self>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let value = serde_cbor::from_slice::<T>(cbor)?;
+        Self::serialize_cbor(&value)
     }
 }
 
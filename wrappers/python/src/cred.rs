@@ -1,10 +1,13 @@
+use std::collections::HashMap;
+
 use pyo3::class::PyObjectProtocol;
 use pyo3::exceptions::ValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyString, PyType};
+use pyo3::types::{PyBool, PyDict, PyString, PyType};
 use pyo3::wrap_pyfunction;
 
 use indy_credx::domain::credential::{Credential, CredentialValues};
+use indy_credx::domain::w3c_credential::W3CVcFormat;
 use indy_credx::services::issuer::{CredentialRevocationConfig, Issuer};
 use indy_credx::services::prover::Prover;
 use indy_credx::services::tails::TailsFileReader;
@@ -18,7 +21,7 @@ use crate::helpers::{PyAcceptBufferArg, PyAcceptJsonArg, PyJsonSafeBuffer};
 use crate::master_secret::PyMasterSecret;
 use crate::rev_reg::{
     PyRevocationPrivateKey, PyRevocationRegistry, PyRevocationRegistryDefinition,
-    PyRevocationRegistryDelta,
+    PyRevocationRegistryDelta, PyRevocationStatusList,
 };
 
 #[pyclass(name=Credential)]
@@ -41,6 +44,33 @@ impl PyCredential {
     pub fn to_json(&self, py: Python) -> PyResult<String> {
         <Self as PyJsonSafeBuffer>::to_json_insecure(self, py)
     }
+
+    /// Exports this credential as a W3C Verifiable Credential, either as a
+    /// JSON-LD document (`format="jsonld"`, the default) or as a compact
+    /// JWT VC (`format="jwt"`)
+    #[args(format = "\"jsonld\"")]
+    pub fn to_w3c_vc(
+        &self,
+        py: Python,
+        issuer_did: &str,
+        subject_did: &str,
+        format: &str,
+    ) -> PyResult<String> {
+        let credential = self.extract_json(py)?;
+        let format = match format {
+            "jsonld" => W3CVcFormat::JsonLd,
+            "jwt" => W3CVcFormat::Jwt,
+            _ => {
+                return Err(PyErr::new::<ValueError, _>(format!(
+                    "Unknown W3C VC format \"{}\", expected \"jsonld\" or \"jwt\"",
+                    format
+                )))
+            }
+        };
+        Ok(credential
+            .to_w3c_vc(issuer_did, subject_did, format)
+            .map_py_err()?)
+    }
 }
 
 #[pyproto]
@@ -63,6 +93,70 @@ impl PyJsonSafeBuffer for PyCredential {
     }
 }
 
+#[pyclass(name=CredentialValues)]
+pub struct PyCredentialValues {
+    pub inner: Py<PySafeBuffer>,
+}
+
+#[pymethods]
+impl PyCredentialValues {
+    #[getter]
+    pub fn buffer(&self, py: Python) -> PyResult<PyObject> {
+        Ok(self.inner.to_object(py))
+    }
+
+    #[classmethod]
+    pub fn from_json(_cls: &PyType, py: Python, json: &PyString) -> PyResult<Self> {
+        <Self as PyJsonSafeBuffer>::from_json_insecure(py, json)
+    }
+
+    pub fn to_json(&self, py: Python) -> PyResult<String> {
+        <Self as PyJsonSafeBuffer>::to_json_insecure(self, py)
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for PyCredentialValues {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("CredentialValues({:p})", self))
+    }
+}
+
+impl From<Py<PySafeBuffer>> for PyCredentialValues {
+    fn from(inner: Py<PySafeBuffer>) -> Self {
+        Self { inner }
+    }
+}
+
+impl PyJsonSafeBuffer for PyCredentialValues {
+    type Inner = CredentialValues;
+    fn buffer(&self, py: Python) -> &PySafeBuffer {
+        self.inner.as_ref(py)
+    }
+}
+
+fn stringify_attr_value(value: &PyAny) -> PyResult<String> {
+    if value.is_none() {
+        Ok(String::new())
+    } else if let Ok(value) = value.downcast_ref::<PyBool>() {
+        Ok(if value.is_true() { "1" } else { "0" }.to_string())
+    } else {
+        Ok(value.str()?.to_string()?.into_owned())
+    }
+}
+
+#[pyfunction]
+/// Builds a CredentialValues buffer from raw attribute name/value pairs,
+/// computing each attribute's CL-signature encoding automatically
+pub fn encode_credential_values(py: Python, attrs: &PyDict) -> PyResult<PyCredentialValues> {
+    let mut values = HashMap::new();
+    for (name, value) in attrs.iter() {
+        values.insert(name.extract::<String>()?, stringify_attr_value(value)?);
+    }
+    let cred_values = CredentialValues::from_raw_values(values);
+    Ok(PyCredentialValues::embed_json(py, &cred_values)?)
+}
+
 #[pyfunction]
 /// Creates a new credential
 pub fn create_credential(
@@ -71,11 +165,9 @@ pub fn create_credential(
     cred_private_key: PyAcceptBufferArg<PyCredentialPrivateKey>,
     cred_offer: PyAcceptJsonArg<PyCredentialOffer>,
     cred_request: PyAcceptJsonArg<PyCredentialRequest>,
-    cred_values: String,
-    /* ^ FIXME add helper to prepare credential values (w/attribute encoding),
-    and pass in safe buffer here */
+    cred_values: PyAcceptBufferArg<PyCredentialValues>,
     rev_reg_def: Option<PyAcceptJsonArg<PyRevocationRegistryDefinition>>,
-    rev_reg: Option<PyAcceptJsonArg<PyRevocationRegistry>>,
+    rev_status_list: Option<PyAcceptJsonArg<PyRevocationStatusList>>,
     rev_reg_key: Option<PyAcceptBufferArg<PyRevocationPrivateKey>>,
     rev_reg_idx: Option<u32>,
     tails_file_path: Option<String>,
@@ -84,22 +176,21 @@ pub fn create_credential(
     Option<PyRevocationRegistry>,
     Option<PyRevocationRegistryDelta>,
 )> {
-    let cred_values =
-        serde_json::from_str::<CredentialValues>(cred_values.as_ref()).map_py_err()?;
+    let cred_values = cred_values.extract_json(py)?;
     let cred_private_key = &cred_private_key.extract_json(py)?;
     let rev_reg_key = rev_reg_key.map(|key| key.extract_json(py)).transpose()?;
     let revocation_config = match (
         &rev_reg_def,
-        &rev_reg,
+        &rev_status_list,
         &rev_reg_key,
         rev_reg_idx,
         &tails_file_path,
     ) {
         (None, None, None, None, None) => None,
-        (Some(reg_def), Some(registry), Some(registry_key), Some(registry_idx), Some(path)) => {
+        (Some(reg_def), Some(status_list), Some(registry_key), Some(registry_idx), Some(path)) => {
             Some(CredentialRevocationConfig {
                 reg_def,
-                registry,
+                status_list,
                 registry_key,
                 registry_idx,
                 tails_reader: TailsFileReader::new(path.as_str()),
@@ -158,8 +249,10 @@ pub fn process_credential(
 }
 
 pub fn register(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(encode_credential_values))?;
     m.add_wrapped(wrap_pyfunction!(create_credential))?;
     m.add_wrapped(wrap_pyfunction!(process_credential))?;
     m.add_class::<PyCredential>()?;
+    m.add_class::<PyCredentialValues>()?;
     Ok(())
 }
@@ -1,16 +1,20 @@
+use std::str::FromStr;
+
 use pyo3::class::PyObjectProtocol;
 use pyo3::prelude::*;
-use pyo3::types::{PyString, PyTuple, PyType};
+use pyo3::types::{PyBytes, PyString, PyTuple, PyType};
 use pyo3::wrap_pyfunction;
 
 use indy_credx::common::did::DidValue;
-use indy_credx::domain::credential_definition::{CredentialDefinition, CredentialDefinitionConfig};
+use indy_credx::domain::credential_definition::{
+    CredentialDefinition, CredentialDefinitionConfig, SignatureType,
+};
 use indy_credx::services as Services;
 use indy_credx::services::issuer::Issuer;
 
 use crate::buffer::PySafeBuffer;
 use crate::error::PyIndyResult;
-use crate::helpers::{PyAcceptJsonArg, PyJsonSafeBuffer};
+use crate::helpers::{PyAcceptJsonArg, PyCborSafeBuffer, PyJsonSafeBuffer};
 use crate::schema::PySchema;
 
 #[pyclass(name=CredentialDefinition)]
@@ -104,6 +108,16 @@ impl PyCredentialPrivateKey {
     pub fn to_json(&self, py: Python) -> PyResult<String> {
         <Self as PyJsonSafeBuffer>::to_json_insecure(self, py)
     }
+
+    #[classmethod]
+    pub fn from_cbor(_cls: &PyType, py: Python, cbor: &PyBytes) -> PyResult<Self> {
+        <Self as PyCborSafeBuffer>::from_cbor_insecure(py, cbor.as_bytes())
+    }
+
+    pub fn to_cbor<'p>(&self, py: Python<'p>) -> PyResult<&'p PyBytes> {
+        let data = <Self as PyCborSafeBuffer>::to_cbor_insecure(self, py)?;
+        Ok(PyBytes::new(py, &data))
+    }
 }
 
 #[pyproto]
@@ -126,6 +140,13 @@ impl PyJsonSafeBuffer for PyCredentialPrivateKey {
     }
 }
 
+impl PyCborSafeBuffer for PyCredentialPrivateKey {
+    type Inner = Services::CredentialPrivateKey;
+    fn buffer(&self, py: Python) -> &PySafeBuffer {
+        self.inner.as_ref(py)
+    }
+}
+
 #[pyclass(name=CredentialKeyCorrectnessProof)]
 #[serde(transparent)]
 #[derive(Serialize, Deserialize)]
@@ -162,6 +183,8 @@ pub fn create_credential_definition(
     origin_did: &PyString,
     schema: PyAcceptJsonArg<PySchema>,
     tag: Option<&PyString>,
+    support_revocation: Option<bool>,
+    signature_type: Option<&PyString>,
 ) -> PyResult<PyObject> {
     let origin_did = origin_did.to_string()?.to_string();
     let tag = if let Some(tag) = tag {
@@ -169,9 +192,13 @@ pub fn create_credential_definition(
     } else {
         "default".to_string()
     };
+    let signature_type = if let Some(signature_type) = signature_type {
+        SignatureType::from_str(signature_type.to_string()?.as_ref()).map_py_err()?
+    } else {
+        SignatureType::CL
+    };
     let config = CredentialDefinitionConfig {
-        signature_type: None,
-        support_revocation: false,
+        support_revocation: support_revocation.unwrap_or(false),
     };
     let (cred_def, private_key, correctness_proof) = py
         .allow_threads(move || {
@@ -179,6 +206,7 @@ pub fn create_credential_definition(
                 &DidValue(origin_did),
                 &schema.inner,
                 tag.as_str(),
+                signature_type,
                 config,
             )
         })
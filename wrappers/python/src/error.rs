@@ -2,9 +2,84 @@ use pyo3::create_exception;
 use pyo3::exceptions::Exception;
 use pyo3::prelude::*;
 
-use indy_credx::common::error::IndyError as LibError;
+use indy_credx::common::error::{IndyError as LibError, IndyErrorKind};
 
-create_exception!(indy_credx, IndyError, Exception);
+create_exception!(indy_credx, IndyCredxError, Exception);
+create_exception!(indy_credx, InputError, IndyCredxError);
+create_exception!(indy_credx, IOError, IndyCredxError);
+create_exception!(indy_credx, InvalidStateError, IndyCredxError);
+create_exception!(indy_credx, UnexpectedError, IndyCredxError);
+create_exception!(indy_credx, CredentialRevokedError, IndyCredxError);
+create_exception!(indy_credx, InvalidUserRevocIdError, IndyCredxError);
+create_exception!(indy_credx, ProofRejectedError, IndyCredxError);
+create_exception!(indy_credx, RevocationRegistryFullError, IndyCredxError);
+
+/// Stable integer code for each `IndyErrorKind`, set as the `code` attribute on
+/// the raised exception so Python code can branch on it instead of matching the
+/// exception message or class hierarchy.
+fn error_code(kind: &IndyErrorKind) -> u32 {
+    match kind {
+        IndyErrorKind::Input => 1,
+        IndyErrorKind::IOError => 2,
+        IndyErrorKind::InvalidState => 3,
+        IndyErrorKind::Unexpected => 4,
+        IndyErrorKind::CredentialRevoked => 5,
+        IndyErrorKind::InvalidUserRevocId => 6,
+        IndyErrorKind::ProofRejected => 7,
+        IndyErrorKind::RevocationRegistryFull => 8,
+    }
+}
+
+/// `IndyError`'s `Display` impl folds its whole `source()` chain into one
+/// string (`"{kind}: {msg}\n{source}"`), which is handy for logs but would
+/// duplicate the source text once we start attaching each cause as its own
+/// Python exception below. This recovers just the top-level message.
+fn own_message(err: &LibError) -> String {
+    let full = err.to_string();
+    if let Some(source) = std::error::Error::source(err) {
+        let suffix = format!("\n{}", source);
+        if full.ends_with(&suffix) {
+            return full[..full.len() - suffix.len()].to_string();
+        }
+    }
+    full
+}
+
+/// Converts a `LibError` into a `PyErr` of the exception subclass matching its
+/// `kind`, then walks the rest of the `source()` chain, wrapping each cause in
+/// its own `IndyCredxError` instance and linking them together via
+/// `__cause__` so Python tracebacks show the full chain that produced the
+/// error instead of one flattened message.
+fn new_py_err(err: LibError) -> PyErr {
+    let py = unsafe { Python::assume_gil_acquired() };
+    let msg = own_message(&err);
+    let top = match err.kind() {
+        IndyErrorKind::Input => PyErr::new::<InputError, _>(msg),
+        IndyErrorKind::IOError => PyErr::new::<IOError, _>(msg),
+        IndyErrorKind::InvalidState => PyErr::new::<InvalidStateError, _>(msg),
+        IndyErrorKind::Unexpected => PyErr::new::<UnexpectedError, _>(msg),
+        IndyErrorKind::CredentialRevoked => PyErr::new::<CredentialRevokedError, _>(msg),
+        IndyErrorKind::InvalidUserRevocId => PyErr::new::<InvalidUserRevocIdError, _>(msg),
+        IndyErrorKind::ProofRejected => PyErr::new::<ProofRejectedError, _>(msg),
+        IndyErrorKind::RevocationRegistryFull => {
+            PyErr::new::<RevocationRegistryFullError, _>(msg)
+        }
+    };
+    let top_instance = top.instance(py);
+    let _ = top_instance.setattr("code", error_code(err.kind()));
+
+    let mut current = top_instance;
+    let mut cause = std::error::Error::source(&err);
+    while let Some(next) = cause {
+        let next_instance = PyErr::new::<IndyCredxError, _>(next.to_string()).instance(py);
+        let _ = current.setattr("__cause__", next_instance);
+        let _ = current.setattr("__suppress_context__", true);
+        current = next_instance;
+        cause = next.source();
+    }
+
+    PyErr::from_instance(top_instance)
+}
 
 pub trait PyIndyResult<T> {
     fn map_py_err(self) -> PyResult<T>;
@@ -21,7 +96,7 @@ where
     fn map_py_err(self) -> PyResult<T> {
         match self {
             Ok(r) => Ok(r),
-            Err(err) => Err(PyErr::new::<IndyError, _>(err.into().to_string())),
+            Err(err) => Err(new_py_err(err.into())),
         }
     }
 
@@ -32,9 +107,32 @@ where
     {
         match self {
             Ok(r) => Ok(r),
-            Err(err) => Err(PyErr::new::<IndyError, _>(
-                err.into().extend(msgfn()).to_string(),
-            )),
+            Err(err) => Err(new_py_err(err.into().extend(msgfn()))),
         }
     }
 }
+
+pub fn register(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("IndyCredxError", _py.get_type::<IndyCredxError>())?;
+    m.add("InputError", _py.get_type::<InputError>())?;
+    m.add("IOError", _py.get_type::<IOError>())?;
+    m.add("InvalidStateError", _py.get_type::<InvalidStateError>())?;
+    m.add("UnexpectedError", _py.get_type::<UnexpectedError>())?;
+    m.add(
+        "CredentialRevokedError",
+        _py.get_type::<CredentialRevokedError>(),
+    )?;
+    m.add(
+        "InvalidUserRevocIdError",
+        _py.get_type::<InvalidUserRevocIdError>(),
+    )?;
+    m.add(
+        "ProofRejectedError",
+        _py.get_type::<ProofRejectedError>(),
+    )?;
+    m.add(
+        "RevocationRegistryFullError",
+        _py.get_type::<RevocationRegistryFullError>(),
+    )?;
+    Ok(())
+}
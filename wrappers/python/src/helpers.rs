@@ -168,3 +168,41 @@ pub trait PyJsonSafeBuffer: From<Py<PySafeBuffer>> + PyTypeInfo {
         Ok(Self::from(inner))
     }
 }
+
+/// Mirrors `PyJsonSafeBuffer` for the compact CBOR encoding, for the
+/// safe-buffer-backed types holding large CL key material where the bulk of
+/// JSON actually matters.
+pub trait PyCborSafeBuffer: From<Py<PySafeBuffer>> + PyTypeInfo {
+    type Inner: serde::de::DeserializeOwned + serde::Serialize;
+
+    fn buffer(&self, py: Python) -> &PySafeBuffer;
+
+    fn embed_cbor(py: Python, value: &Self::Inner) -> PyResult<Self> {
+        Ok(Self::from(Py::new(
+            py,
+            PySafeBuffer::serialize_cbor(value)
+                .map_py_err_msg(|| format!("Error parsing {} as CBOR", Self::NAME))?,
+        )?))
+    }
+
+    fn extract_cbor(&self, py: Python) -> PyResult<Self::Inner> {
+        self.buffer(py)
+            .deserialize()
+            .map_py_err_msg(|| format!("Error parsing {} as CBOR", Self::NAME))
+    }
+
+    fn to_cbor_insecure(&self, py: Python) -> PyResult<Vec<u8>> {
+        self.buffer(py)
+            .to_cbor_insecure::<Self::Inner>()
+            .map_py_err_msg(|| format!("Error serializing {} as CBOR", Self::NAME))
+    }
+
+    fn from_cbor_insecure(py: Python, cbor: &[u8]) -> PyResult<Self> {
+        let inner = Py::new(
+            py,
+            PySafeBuffer::from_cbor_insecure::<Self::Inner>(cbor)
+                .map_py_err_msg(|| format!("Error parsing {} as CBOR", Self::NAME))?,
+        )?;
+        Ok(Self::from(inner))
+    }
+}
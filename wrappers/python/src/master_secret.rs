@@ -1,6 +1,6 @@
 use pyo3::class::PyObjectProtocol;
 use pyo3::prelude::*;
-use pyo3::types::{PyString, PyType};
+use pyo3::types::{PyBytes, PyString, PyType};
 use pyo3::wrap_pyfunction;
 
 use indy_credx::services::prover::Prover;
@@ -8,7 +8,7 @@ use indy_credx::services::MasterSecret;
 
 use crate::buffer::PySafeBuffer;
 use crate::error::PyIndyResult;
-use crate::helpers::PyJsonSafeBuffer;
+use crate::helpers::{PyCborSafeBuffer, PyJsonSafeBuffer};
 
 #[pyclass(name=MasterSecret)]
 pub struct PyMasterSecret {
@@ -24,11 +24,21 @@ impl PyMasterSecret {
 
     #[classmethod]
     pub fn from_json(_cls: &PyType, py: Python, json: &PyString) -> PyResult<Self> {
-        <Self as PyJsonSafeBuffer>::from_json(py, json)
+        <Self as PyJsonSafeBuffer>::from_json_insecure(py, json)
     }
 
     pub fn to_json(&self, py: Python) -> PyResult<String> {
-        <Self as PyJsonSafeBuffer>::to_json(self, py)
+        <Self as PyJsonSafeBuffer>::to_json_insecure(self, py)
+    }
+
+    #[classmethod]
+    pub fn from_cbor(_cls: &PyType, py: Python, cbor: &PyBytes) -> PyResult<Self> {
+        <Self as PyCborSafeBuffer>::from_cbor_insecure(py, cbor.as_bytes())
+    }
+
+    pub fn to_cbor<'p>(&self, py: Python<'p>) -> PyResult<&'p PyBytes> {
+        let data = <Self as PyCborSafeBuffer>::to_cbor_insecure(self, py)?;
+        Ok(PyBytes::new(py, &data))
     }
 }
 
@@ -52,6 +62,13 @@ impl PyJsonSafeBuffer for PyMasterSecret {
     }
 }
 
+impl PyCborSafeBuffer for PyMasterSecret {
+    type Inner = MasterSecret;
+    fn buffer(&self, py: Python) -> &PySafeBuffer {
+        self.inner.as_ref(py)
+    }
+}
+
 #[pyfunction]
 /// Creates a new master secret
 pub fn create_master_secret(py: Python) -> PyResult<PyMasterSecret> {
@@ -5,15 +5,17 @@ use pyo3::prelude::*;
 use pyo3::types::{PyString, PyType};
 use pyo3::wrap_pyfunction;
 
+use indy_credx::domain::credential::Credential;
 use indy_credx::domain::proof::Proof;
 use indy_credx::domain::proof_request::ProofRequest;
 use indy_credx::domain::requested_credential::RequestedCredentials;
+use indy_credx::domain::w3c_presentation::VerifiablePresentation;
 use indy_credx::identifiers::cred_def::CredentialDefinitionId;
-use indy_credx::identifiers::rev_reg::RevocationRegistryId;
+use indy_credx::identifiers::rev_reg_def::RevocationRegistryDefinitionId;
 use indy_credx::identifiers::schema::SchemaId;
 use indy_credx::services::new_nonce;
 use indy_credx::services::prover::Prover;
-use indy_credx::services::verifier::Verifier;
+use indy_credx::services::verifier::{VerificationPolicy, Verifier};
 
 use crate::buffer::PySafeBuffer;
 use crate::cred::PyCredential;
@@ -21,7 +23,10 @@ use crate::cred_def::PyCredentialDefinition;
 use crate::error::PyIndyResult;
 use crate::helpers::{PyAcceptBufferArg, PyAcceptJsonArg, PyJsonArg, PyJsonSafeBuffer};
 use crate::master_secret::PyMasterSecret;
-use crate::rev_reg::{PyRevocationRegistry, PyRevocationRegistryDefinition, PyRevocationState};
+use crate::rev_reg::{
+    PyRevocationRegistry, PyRevocationRegistryDefinition, PyRevocationState,
+    PyRevocationStatusList,
+};
 use crate::schema::PySchema;
 
 #[pyclass(name=Proof)]
@@ -44,6 +49,48 @@ impl PyProof {
     pub fn to_json(&self, py: Python) -> PyResult<String> {
         <Self as PyJsonSafeBuffer>::to_json_insecure(self, py)
     }
+
+    /// Exports this proof as a W3C `VerifiablePresentation` JSON document:
+    /// one `verifiableCredential` entry per sub-proof, with the CL proof and
+    /// request nonce attached under an anoncreds-specific `proof` object.
+    pub fn to_w3c_presentation(
+        &self,
+        py: Python,
+        proof_req: PyAcceptJsonArg<PyProofRequest>,
+        schemas: HashMap<String, PyAcceptJsonArg<PySchema>>,
+        cred_defs: HashMap<String, PyAcceptJsonArg<PyCredentialDefinition>>,
+    ) -> PyResult<String> {
+        let proof = self.extract_json(py)?;
+        let schema_refs = schemas
+            .iter()
+            .map(|(k, schema)| (SchemaId(k.clone()), &schema.inner))
+            .collect();
+        let cred_def_refs = cred_defs
+            .iter()
+            .map(|(k, cdef)| (CredentialDefinitionId(k.clone()), &cdef.inner))
+            .collect();
+        let presentation = proof
+            .to_w3c_presentation(proof_req.value(), &schema_refs, &cred_def_refs)
+            .map_py_err()?;
+        Ok(serde_json::to_string(&presentation).map_py_err()?)
+    }
+
+    /// Recovers the native `Proof` a `VerifiablePresentation` document was
+    /// built from, the exact inverse of `to_w3c_presentation`, so the result
+    /// can be handed to `verify_proof`.
+    #[classmethod]
+    pub fn from_w3c_presentation(
+        _cls: &PyType,
+        py: Python,
+        presentation: &PyString,
+    ) -> PyResult<Self> {
+        let presentation = serde_json::from_str::<VerifiablePresentation>(
+            &presentation.to_string()?,
+        )
+        .map_py_err_msg(|| "Error parsing W3C presentation JSON")?;
+        let proof = Proof::from_w3c_presentation(&presentation).map_py_err()?;
+        Ok(PyProof::embed_json(py, &proof)?)
+    }
 }
 
 #[pyproto]
@@ -107,6 +154,29 @@ impl std::ops::Deref for PyProofRequest {
     }
 }
 
+#[pyfunction]
+/// Searches a prover's stored credentials against a proof request, returning
+/// for each requested attribute and predicate referent the ids of the
+/// credentials that could satisfy it. Does not pick a credential or build a
+/// proof; feed the result into a `RequestedCredentials` for `create_proof`.
+pub fn search_credentials_for_proof_request(
+    py: Python,
+    proof_req: PyAcceptJsonArg<PyProofRequest>,
+    credentials: HashMap<String, PyAcceptBufferArg<PyCredential>>,
+) -> PyResult<(HashMap<String, Vec<String>>, HashMap<String, Vec<String>>)> {
+    let credentials: HashMap<String, Credential> =
+        credentials
+            .into_iter()
+            .try_fold(HashMap::new(), |mut map, (k, cred)| -> PyResult<_> {
+                map.insert(k, cred.extract_json(py)?);
+                Ok(map)
+            })?;
+    let result = py
+        .allow_threads(move || Prover::search_credentials_for_proof_request(&proof_req, &credentials))
+        .map_py_err()?;
+    Ok((result.requested_attributes, result.requested_predicates))
+}
+
 #[pyfunction]
 /// Creates a new proof
 pub fn create_proof(
@@ -181,6 +251,10 @@ pub fn verify_proof(
     cred_defs: HashMap<String, PyAcceptJsonArg<PyCredentialDefinition>>,
     rev_reg_defs: Option<HashMap<String, PyAcceptJsonArg<PyRevocationRegistryDefinition>>>,
     rev_regs: Option<HashMap<String, HashMap<u64, PyAcceptJsonArg<PyRevocationRegistry>>>>,
+    rev_status_lists: Option<HashMap<String, PyAcceptJsonArg<PyRevocationStatusList>>>,
+    timestamp_tolerance: Option<u64>,
+    allow_self_attested: Option<bool>,
+    self_attested_denylist: Option<Vec<String>>,
 ) -> PyResult<bool> {
     let proof = proof.extract_json(py)?;
     let schema_refs = schemas
@@ -194,7 +268,7 @@ pub fn verify_proof(
     let rev_reg_def_refs = if let Some(rev_reg_defs) = rev_reg_defs.as_ref() {
         rev_reg_defs
             .iter()
-            .map(|(k, rdef)| (RevocationRegistryId(k.clone()), &rdef.inner))
+            .map(|(k, rdef)| (RevocationRegistryDefinitionId(k.clone()), &rdef.inner))
             .collect()
     } else {
         HashMap::new()
@@ -203,7 +277,7 @@ pub fn verify_proof(
         rev_regs
             .iter()
             .map(|(k, reg_map)| {
-                (RevocationRegistryId(k.clone()), {
+                (RevocationRegistryDefinitionId(k.clone()), {
                     reg_map
                         .into_iter()
                         .map(|(ts, reg)| (*ts, &reg.inner))
@@ -214,6 +288,19 @@ pub fn verify_proof(
     } else {
         HashMap::new()
     };
+    let rev_status_list_refs = rev_status_lists.as_ref().map(|rev_status_lists| {
+        rev_status_lists
+            .iter()
+            .map(|(k, list)| (RevocationRegistryDefinitionId(k.clone()), &list.inner))
+            .collect::<HashMap<_, _>>()
+    });
+    let policy = VerificationPolicy {
+        allow_self_attested: allow_self_attested.unwrap_or(true),
+        self_attested_denylist: self_attested_denylist
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+    };
     let verified = py
         .allow_threads(move || {
             Verifier::verify_proof(
@@ -223,6 +310,9 @@ pub fn verify_proof(
                 &cred_def_refs,
                 &rev_reg_def_refs,
                 &rev_reg_refs,
+                rev_status_list_refs.as_ref(),
+                timestamp_tolerance,
+                Some(&policy),
             )
         })
         .map_py_err()?;
@@ -230,6 +320,7 @@ pub fn verify_proof(
 }
 
 pub fn register(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(search_credentials_for_proof_request))?;
     m.add_wrapped(wrap_pyfunction!(create_proof))?;
     m.add_wrapped(wrap_pyfunction!(generate_nonce))?;
     m.add_wrapped(wrap_pyfunction!(verify_proof))?;
@@ -1,29 +1,103 @@
 use pyo3::class::PyObjectProtocol;
 use pyo3::prelude::*;
-use pyo3::types::{PyString, PyType};
+use pyo3::types::{PyBytes, PyString, PyType};
 use pyo3::wrap_pyfunction;
 
 use std::collections::HashSet;
+use std::io::Read;
 use std::iter::FromIterator;
 use std::str::FromStr;
 
 use indy_credx::common::did::DidValue;
+use indy_credx::common::error::{err_msg, IndyErrorKind, IndyResult};
 use indy_credx::domain::revocation_registry::RevocationRegistry;
 use indy_credx::domain::revocation_registry_definition::{
-    IssuanceType, RegistryType, RevocationRegistryDefinition,
+    IssuanceType, RegistryType, RevocationRegistryDefinition, RevocationRegistryDefinitionPrivate,
 };
 use indy_credx::domain::revocation_registry_delta::RevocationRegistryDelta;
 use indy_credx::domain::revocation_state::RevocationState;
+use indy_credx::domain::revocation_status_list::RevocationStatusList;
 use indy_credx::services::issuer::Issuer;
 use indy_credx::services::prover::Prover;
-use indy_credx::services::tails::{TailsFileReader, TailsFileWriter};
+use indy_credx::services::tails::{
+    BlobStorage, TailsFileReader, TailsFileWriter, TailsReader, TailsReaderImpl, TailsWriter,
+};
 use indy_credx::services::RevocationKeyPrivate;
 use indy_credx::utils::validation::Validatable;
 
+/// Adapts a Python object exposing a `read(size, offset) -> bytes` method to
+/// the `TailsReaderImpl` trait, so tails blobs kept in memory or fetched from
+/// an object store can be read without ever touching the local filesystem.
+#[derive(Debug)]
+struct PyTailsReaderImpl {
+    handle: PyObject,
+}
+
+impl TailsReaderImpl for PyTailsReaderImpl {
+    fn read(&mut self, size: usize, offset: usize) -> IndyResult<Vec<u8>> {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        self.handle
+            .call_method1(py, "read", (size, offset))
+            .and_then(|obj| obj.extract::<Vec<u8>>(py))
+            .map_err(|err| {
+                err_msg(
+                    IndyErrorKind::IOError,
+                    format!("Error reading from Python tails reader: {}", err),
+                )
+            })
+    }
+}
+
+fn tails_reader_from_handle(handle: PyObject) -> TailsReader {
+    TailsReader::new(PyTailsReaderImpl { handle })
+}
+
+/// Adapts a Python object exposing `put(data: bytes) -> (location, hash)` and
+/// `open(location, hash) -> <reader handle>` methods to the `BlobStorage`
+/// trait, letting the Python layer back tails storage with anything from an
+/// in-memory buffer to an S3-style blob store.
+struct PyBlobStorage {
+    handle: PyObject,
+}
+
+impl BlobStorage for PyBlobStorage {
+    fn put(&mut self, source: &mut dyn Read) -> IndyResult<(String, String)> {
+        let mut buf = Vec::new();
+        source.read_to_end(&mut buf)?;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        self.handle
+            .call_method1(py, "put", (PyBytes::new(py, &buf),))
+            .and_then(|obj| obj.extract::<(String, String)>(py))
+            .map_err(|err| {
+                err_msg(
+                    IndyErrorKind::IOError,
+                    format!("Error writing to Python tails writer: {}", err),
+                )
+            })
+    }
+
+    fn open(&self, location: &str, hash: &str) -> IndyResult<TailsReader> {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let reader_handle = self
+            .handle
+            .call_method1(py, "open", (location, hash))
+            .map_err(|err| {
+                err_msg(
+                    IndyErrorKind::IOError,
+                    format!("Error opening Python tails reader: {}", err),
+                )
+            })?;
+        Ok(tails_reader_from_handle(reader_handle))
+    }
+}
+
 use crate::buffer::PySafeBuffer;
 use crate::cred_def::PyCredentialDefinition;
 use crate::error::PyIndyResult;
-use crate::helpers::{PyAcceptJsonArg, PyJsonSafeBuffer};
+use crate::helpers::{PyAcceptJsonArg, PyCborSafeBuffer, PyJsonSafeBuffer};
 
 #[pyclass(name=RevocationRegistry)]
 #[serde(transparent)]
@@ -137,6 +211,48 @@ impl std::ops::Deref for PyRevocationRegistryDefinition {
     }
 }
 
+#[pyclass(name=RevocationRegistryDefinitionPrivate)]
+#[serde(transparent)]
+#[derive(Serialize, Deserialize)]
+pub struct PyRevocationRegistryDefinitionPrivate {
+    pub inner: RevocationRegistryDefinitionPrivate,
+}
+
+#[pymethods]
+impl PyRevocationRegistryDefinitionPrivate {
+    #[classmethod]
+    pub fn from_json(_cls: &PyType, json: &PyString) -> PyResult<Self> {
+        let inner =
+            serde_json::from_str::<RevocationRegistryDefinitionPrivate>(&json.to_string()?)
+                .map_py_err_msg(|| "Error parsing revocation registry definition private JSON")?;
+        Ok(Self { inner })
+    }
+
+    pub fn to_json(&self) -> PyResult<String> {
+        Ok(serde_json::to_string(&self.inner).map_py_err()?)
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for PyRevocationRegistryDefinitionPrivate {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("RevocationRegistryDefinitionPrivate({:p})", self))
+    }
+}
+
+impl From<RevocationRegistryDefinitionPrivate> for PyRevocationRegistryDefinitionPrivate {
+    fn from(value: RevocationRegistryDefinitionPrivate) -> Self {
+        Self { inner: value }
+    }
+}
+
+impl std::ops::Deref for PyRevocationRegistryDefinitionPrivate {
+    type Target = RevocationRegistryDefinitionPrivate;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
 #[pyclass(name=RevocationPrivateKey)]
 pub struct PyRevocationPrivateKey {
     inner: Py<PySafeBuffer>,
@@ -157,6 +273,16 @@ impl PyRevocationPrivateKey {
     pub fn to_json(&self, py: Python) -> PyResult<String> {
         <Self as PyJsonSafeBuffer>::to_json_insecure(self, py)
     }
+
+    #[classmethod]
+    pub fn from_cbor(_cls: &PyType, py: Python, cbor: &PyBytes) -> PyResult<Self> {
+        <Self as PyCborSafeBuffer>::from_cbor_insecure(py, cbor.as_bytes())
+    }
+
+    pub fn to_cbor<'p>(&self, py: Python<'p>) -> PyResult<&'p PyBytes> {
+        let data = <Self as PyCborSafeBuffer>::to_cbor_insecure(self, py)?;
+        Ok(PyBytes::new(py, &data))
+    }
 }
 
 #[pyproto]
@@ -179,6 +305,13 @@ impl PyJsonSafeBuffer for PyRevocationPrivateKey {
     }
 }
 
+impl PyCborSafeBuffer for PyRevocationPrivateKey {
+    type Inner = RevocationKeyPrivate;
+    fn buffer(&self, py: Python) -> &PySafeBuffer {
+        self.inner.as_ref(py)
+    }
+}
+
 #[pyclass(name=RevocationRegistryDelta)]
 #[serde(transparent)]
 #[derive(Serialize, Deserialize)]
@@ -234,6 +367,42 @@ impl PyRevocationState {
         self.inner.timestamp
     }
 
+    /// Advances this witness to match a freshly published
+    /// `RevocationRegistryDelta`, returning a new `RevocationState` without
+    /// needing to rebuild it from the full tails file. `revoked` must be the
+    /// same index set the issuer published alongside `rev_reg_delta`.
+    pub fn update(
+        &self,
+        py: Python,
+        revoc_reg_def: PyAcceptJsonArg<PyRevocationRegistryDefinition>,
+        rev_reg_delta: PyAcceptJsonArg<PyRevocationRegistryDelta>,
+        rev_reg_idx: u32,
+        timestamp: u64,
+        tails_file_path: String,
+        revoked: Option<Vec<u32>>,
+        tails_reader_handle: Option<PyObject>,
+    ) -> PyResult<PyRevocationState> {
+        let rev_state = self.inner.clone();
+        let rev_state = py
+            .allow_threads(move || {
+                let revoked = HashSet::from_iter(revoked.unwrap_or_else(Vec::new).into_iter());
+                let tails_reader = tails_reader_handle
+                    .map(tails_reader_from_handle)
+                    .unwrap_or_else(|| TailsFileReader::new(tails_file_path.as_str()));
+                Prover::update_revocation_state(
+                    tails_reader,
+                    &revoc_reg_def,
+                    &rev_reg_delta,
+                    &revoked,
+                    rev_reg_idx,
+                    timestamp,
+                    rev_state,
+                )
+            })
+            .map_py_err()?;
+        Ok(PyRevocationState::from(rev_state))
+    }
+
     #[classmethod]
     pub fn from_json(_cls: &PyType, json: &PyString) -> PyResult<Self> {
         let inner = serde_json::from_str::<RevocationState>(&json.to_string()?)
@@ -266,6 +435,61 @@ impl std::ops::Deref for PyRevocationState {
     }
 }
 
+#[pyclass(name=RevocationStatusList)]
+#[serde(transparent)]
+#[derive(Serialize, Deserialize)]
+pub struct PyRevocationStatusList {
+    pub inner: RevocationStatusList,
+}
+
+#[pymethods]
+impl PyRevocationStatusList {
+    #[getter]
+    pub fn rev_reg_def_id(&self) -> PyResult<String> {
+        Ok(self.inner.rev_reg_def_id.to_string())
+    }
+
+    #[getter]
+    pub fn timestamp(&self) -> u64 {
+        self.inner.timestamp
+    }
+
+    #[classmethod]
+    pub fn from_json(_cls: &PyType, json: &PyString) -> PyResult<Self> {
+        let inner = serde_json::from_str::<RevocationStatusList>(&json.to_string()?)
+            .map_py_err_msg(|| "Error parsing revocation status list JSON")?;
+        Ok(Self { inner })
+    }
+
+    pub fn to_json(&self) -> PyResult<String> {
+        Ok(serde_json::to_string(&self.inner).map_py_err()?)
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for PyRevocationStatusList {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "RevocationStatusList({}, {})",
+            self.rev_reg_def_id()?,
+            self.timestamp()
+        ))
+    }
+}
+
+impl From<RevocationStatusList> for PyRevocationStatusList {
+    fn from(value: RevocationStatusList) -> Self {
+        Self { inner: value }
+    }
+}
+
+impl std::ops::Deref for PyRevocationStatusList {
+    type Target = RevocationStatusList;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
 #[pyfunction]
 /// Creates a new revocation registry
 fn create_revocation_registry(
@@ -277,6 +501,7 @@ fn create_revocation_registry(
     max_cred_num: u32,
     issuance_type: Option<String>,
     tails_dir_path: Option<String>,
+    tails_writer_handle: Option<PyObject>,
 ) -> PyResult<(
     PyRevocationRegistryDefinition,
     PyRevocationRegistry,
@@ -291,18 +516,31 @@ fn create_revocation_registry(
         .map_py_err()?
         .unwrap_or(IssuanceType::ISSUANCE_BY_DEFAULT);
     let tag = tag.unwrap_or_else(|| "default".to_owned()); // FIXME
-    let mut tails_writer = TailsFileWriter::new(tails_dir_path);
     let (rev_reg_def, rev_reg, rev_private_key) = py
         .allow_threads(move || {
-            Issuer::new_revocation_registry(
-                &origin_did,
-                &cred_def,
-                tag.as_str(),
-                rev_reg_type,
-                issuance_type,
-                max_cred_num,
-                &mut tails_writer,
-            )
+            if let Some(handle) = tails_writer_handle {
+                let mut tails_writer = TailsWriter::new(PyBlobStorage { handle });
+                Issuer::new_revocation_registry(
+                    &origin_did,
+                    &cred_def,
+                    tag.as_str(),
+                    rev_reg_type,
+                    issuance_type,
+                    max_cred_num,
+                    &mut tails_writer,
+                )
+            } else {
+                let mut tails_writer = TailsFileWriter::new(tails_dir_path);
+                Issuer::new_revocation_registry(
+                    &origin_did,
+                    &cred_def,
+                    tag.as_str(),
+                    rev_reg_type,
+                    issuance_type,
+                    max_cred_num,
+                    &mut tails_writer,
+                )
+            }
         })
         .map_py_err_msg(|| "Error creating revocation registry")?; // FIXME combine error
     Ok((
@@ -322,12 +560,16 @@ fn create_or_update_revocation_state(
     timestamp: u64,
     tails_file_path: String,
     rev_state: Option<PyAcceptJsonArg<PyRevocationState>>,
+    tails_reader_handle: Option<PyObject>,
 ) -> PyResult<PyRevocationState> {
     let rev_state = rev_state.map(|state| state.clone());
     let rev_state = py
         .allow_threads(move || {
+            let tails_reader = tails_reader_handle
+                .map(tails_reader_from_handle)
+                .unwrap_or_else(|| TailsFileReader::new(tails_file_path.as_str()));
             Prover::create_or_update_revocation_state(
-                TailsFileReader::new(tails_file_path.as_str()),
+                tails_reader,
                 &revoc_reg_def,
                 &rev_reg_delta,
                 rev_reg_idx,
@@ -348,12 +590,15 @@ fn update_revocation_registry(
     issued: Option<Vec<u32>>,
     revoked: Option<Vec<u32>>,
     tails_file_path: String,
+    tails_reader_handle: Option<PyObject>,
 ) -> PyResult<(PyRevocationRegistry, PyRevocationRegistryDelta)> {
     let (rev_reg, rev_reg_delta) = py
         .allow_threads(move || {
             let issued = HashSet::from_iter(issued.unwrap_or_else(|| vec![]).into_iter());
             let revoked = HashSet::from_iter(revoked.unwrap_or_else(|| vec![]).into_iter());
-            let tails_reader = TailsFileReader::new(tails_file_path.as_str());
+            let tails_reader = tails_reader_handle
+                .map(tails_reader_from_handle)
+                .unwrap_or_else(|| TailsFileReader::new(tails_file_path.as_str()));
             Issuer::update_revocation_registry(
                 &rev_reg_def,
                 &rev_reg,
@@ -369,12 +614,127 @@ fn update_revocation_registry(
     ))
 }
 
+#[pyfunction]
+/// Revokes a credential in a revocation registry
+fn revoke_credential(
+    py: Python,
+    rev_reg: PyAcceptJsonArg<PyRevocationRegistry>,
+    max_cred_num: u32,
+    rev_idx: u32,
+    tails_file_path: String,
+    tails_reader_handle: Option<PyObject>,
+) -> PyResult<PyRevocationRegistryDelta> {
+    let delta = py
+        .allow_threads(move || {
+            let tails_reader = tails_reader_handle
+                .map(tails_reader_from_handle)
+                .unwrap_or_else(|| TailsFileReader::new(tails_file_path.as_str()));
+            Issuer {}.revoke(&rev_reg, max_cred_num, rev_idx, &tails_reader)
+        })
+        .map_py_err_msg(|| "Error revoking credential")?;
+    Ok(PyRevocationRegistryDelta::from(delta))
+}
+
+#[pyfunction]
+/// Creates a new revocation status list snapshot for a freshly-created registry
+fn create_revocation_status_list(
+    rev_reg_def: PyAcceptJsonArg<PyRevocationRegistryDefinition>,
+    rev_reg: PyAcceptJsonArg<PyRevocationRegistry>,
+    issuance_type: Option<String>,
+    timestamp: u64,
+) -> PyResult<PyRevocationStatusList> {
+    let issuance_type = issuance_type
+        .map(|it| IssuanceType::from_str(it.as_str()))
+        .transpose()
+        .map_py_err()?
+        .unwrap_or(IssuanceType::ISSUANCE_BY_DEFAULT);
+    let status_list =
+        Issuer::create_revocation_status_list(&rev_reg_def, &rev_reg, issuance_type, timestamp)
+            .map_py_err_msg(|| "Error creating revocation status list")?;
+    Ok(PyRevocationStatusList::from(status_list))
+}
+
+#[pyfunction]
+/// Updates a revocation status list snapshot to reflect newly issued/revoked indices
+fn update_revocation_status_list(
+    py: Python,
+    rev_reg_def: PyAcceptJsonArg<PyRevocationRegistryDefinition>,
+    prev_list: PyAcceptJsonArg<PyRevocationStatusList>,
+    issued: Option<Vec<u32>>,
+    revoked: Option<Vec<u32>>,
+    tails_file_path: String,
+    timestamp: u64,
+    tails_reader_handle: Option<PyObject>,
+) -> PyResult<PyRevocationStatusList> {
+    let status_list = py
+        .allow_threads(move || {
+            let issued = HashSet::from_iter(issued.unwrap_or_else(Vec::new).into_iter());
+            let revoked = HashSet::from_iter(revoked.unwrap_or_else(Vec::new).into_iter());
+            let tails_reader = tails_reader_handle
+                .map(tails_reader_from_handle)
+                .unwrap_or_else(|| TailsFileReader::new(tails_file_path.as_str()));
+            Issuer::update_revocation_status_list(
+                &rev_reg_def,
+                &prev_list,
+                issued,
+                revoked,
+                &tails_reader,
+                timestamp,
+            )
+        })
+        .map_py_err_msg(|| "Error updating revocation status list")?;
+    Ok(PyRevocationStatusList::from(status_list))
+}
+
+#[pyfunction]
+/// Merges an earlier revocation registry delta into a later one
+fn merge_deltas(
+    newer: PyAcceptJsonArg<PyRevocationRegistryDelta>,
+    older: PyAcceptJsonArg<PyRevocationRegistryDelta>,
+) -> PyResult<PyRevocationRegistryDelta> {
+    let merged = Issuer::merge_revocation_registry_deltas(&older, &newer)
+        .map_py_err_msg(|| "Error merging revocation registry deltas")?;
+    Ok(PyRevocationRegistryDelta::from(merged))
+}
+
+#[pyfunction]
+/// Folds a chronologically-ordered list of revocation registry deltas (oldest
+/// first) into a single delta spanning the full range, following the Indy
+/// anoncreds `issuer_merge_revocation_registry_deltas` pattern. Lets a holder
+/// or verifier apply one consolidated witness update instead of replaying
+/// each per-credential delta, which matters for registries that have issued
+/// thousands of credentials.
+fn merge_revocation_registry_deltas(
+    deltas: Vec<PyAcceptJsonArg<PyRevocationRegistryDelta>>,
+) -> PyResult<PyRevocationRegistryDelta> {
+    let mut deltas = deltas.into_iter();
+    let first = deltas.next().ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::ValueError, _>(
+            "Must provide at least one revocation registry delta to merge",
+        )
+    })?;
+    let merged = deltas.try_fold((*first).clone(), |earlier, later| {
+        Issuer::merge_revocation_registry_deltas(&earlier, &later)
+    })
+    .map_py_err_msg(|| "Error merging revocation registry deltas")?;
+    Ok(PyRevocationRegistryDelta::from(merged))
+}
+
 pub fn register(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(create_revocation_registry))?;
     m.add_wrapped(wrap_pyfunction!(create_or_update_revocation_state))?;
     m.add_wrapped(wrap_pyfunction!(update_revocation_registry))?;
+    m.add_wrapped(wrap_pyfunction!(revoke_credential))?;
+    m.add_wrapped(wrap_pyfunction!(create_revocation_status_list))?;
+    m.add_wrapped(wrap_pyfunction!(update_revocation_status_list))?;
+    m.add_wrapped(wrap_pyfunction!(merge_deltas))?;
+    m.add_wrapped(wrap_pyfunction!(merge_revocation_registry_deltas))?;
     m.add_class::<PyRevocationRegistry>()?;
     m.add_class::<PyRevocationRegistryDefinition>()?;
+    m.add_class::<PyRevocationRegistryDefinitionPrivate>()?;
     m.add_class::<PyRevocationPrivateKey>()?;
+    m.add_class::<PyRevocationRegistryDelta>()?;
+    m.add_class::<PyRevocationState>()?;
+    m.add_class::<PyRevocationStatusList>()?;
     Ok(())
 }